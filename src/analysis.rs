@@ -0,0 +1,130 @@
+#[cfg(test)]
+use crate::analysis::fft::{PowerSpectrum, get_block_power_spectrum};
+use std::f64::consts::PI;
+use std::time::Instant;
+
+pub mod cross_correlation;
+pub mod dc_offset;
+pub mod distortion;
+pub mod fft;
+pub mod frequency;
+pub mod hum;
+pub mod noise;
+pub mod spectrum;
+pub mod stereo_image;
+pub mod weighting;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CapturedSamples {
+    pub left: Vec<f32>,
+    pub right: Vec<f32>,
+    pub sample_rate: f32,
+    pub captured_at: Option<Instant>,
+}
+
+impl CapturedSamples {
+    pub fn has_right_channel(&self) -> bool {
+        !self.right.is_empty()
+    }
+}
+
+pub fn get_tone_amplitude_at_frequency(samples: &[f32], frequency: f32, sample_rate: f32) -> f32 {
+    if samples.len() < 2 || sample_rate <= 0.0 {
+        return 0.0;
+    }
+
+    let number_of_samples = samples.len();
+    let coefficient = 2.0 * (2.0 * PI * f64::from(frequency) / f64::from(sample_rate)).cos();
+    let mut window_sum = 0.0;
+    let mut previous = 0.0;
+    let mut before_previous = 0.0;
+
+    for (index, sample) in samples.iter().enumerate() {
+        let window = get_hann_window_value(index, number_of_samples);
+        window_sum += window;
+
+        let current = f64::from(*sample) * window + coefficient * previous - before_previous;
+        before_previous = previous;
+        previous = current;
+    }
+
+    let power =
+        previous * previous + before_previous * before_previous - coefficient * previous * before_previous;
+
+    (2.0 * power.max(0.0).sqrt() / window_sum) as f32
+}
+
+pub fn get_tone_level_at_frequency(samples: &[f32], frequency: f32, sample_rate: f32) -> f32 {
+    get_dbfs_from_amplitude(get_tone_amplitude_at_frequency(
+        samples,
+        frequency,
+        sample_rate,
+    ))
+}
+
+pub fn get_dbfs_from_amplitude(amplitude: f32) -> f32 {
+    20.0 * amplitude.abs().log10()
+}
+
+pub fn get_hann_window_value(index: usize, number_of_samples: usize) -> f64 {
+    if number_of_samples < 2 {
+        return 1.0;
+    }
+    0.5 - 0.5 * (2.0 * PI * index as f64 / (number_of_samples - 1) as f64).cos()
+}
+
+#[cfg(test)]
+pub(crate) fn generate_sine_samples(
+    frequency: f32,
+    amplitude: f32,
+    phase: f32,
+    sample_rate: f32,
+    length: usize,
+) -> Vec<f32> {
+    (0..length)
+        .map(|index| {
+            amplitude
+                * (2.0 * std::f32::consts::PI * frequency * index as f32 / sample_rate + phase).sin()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) fn generate_sine_spectrum(frequency: f32, amplitude: f32, sample_rate: f32, length: usize) -> PowerSpectrum {
+    let samples = generate_sine_samples(frequency, amplitude, 0.0, sample_rate, length);
+    get_block_power_spectrum(&samples, sample_rate).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn return_correct_tone_level_of_sine_wave_at_its_frequency() {
+        let samples = generate_sine_samples(1000.0, 0.5, 0.0, 48000.0, 10240);
+        let level = get_tone_level_at_frequency(&samples, 1000.0, 48000.0);
+        let expected_level = get_dbfs_from_amplitude(0.5);
+        assert!((level - expected_level).abs() < 0.01);
+    }
+
+    #[test]
+    fn return_correct_tone_level_of_low_frequency_sine_wave() {
+        let samples = generate_sine_samples(20.0, 0.25, 0.0, 48000.0, 10240);
+        let level = get_tone_level_at_frequency(&samples, 20.0, 48000.0);
+        let expected_level = get_dbfs_from_amplitude(0.25);
+        assert!((level - expected_level).abs() < 0.05);
+    }
+
+    #[test]
+    fn return_low_tone_level_away_from_the_sine_wave_frequency() {
+        let samples = generate_sine_samples(1000.0, 0.5, 0.0, 48000.0, 10240);
+        let level = get_tone_level_at_frequency(&samples, 3000.0, 48000.0);
+        assert!(level < -80.0);
+    }
+
+    #[test]
+    fn return_zero_amplitude_when_samples_are_empty() {
+        let amplitude = get_tone_amplitude_at_frequency(&[], 1000.0, 48000.0);
+        assert_eq!(amplitude, 0.0);
+    }
+}
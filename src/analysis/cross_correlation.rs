@@ -0,0 +1,140 @@
+use crate::analysis::fft::transform_in_place;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CorrelationPeak {
+    pub lag: usize,
+    pub interpolated_lag: f32,
+    pub is_inverted: bool,
+    pub peak_to_average_ratio: f32,
+}
+
+pub fn get_cross_correlation_peak(signal: &[f32], reference: &[f32]) -> Option<CorrelationPeak> {
+    if reference.is_empty() || signal.len() < reference.len() {
+        return None;
+    }
+
+    let correlation = get_cross_correlation(signal, reference);
+    let valid_lags = &correlation[..=signal.len() - reference.len()];
+
+    let (lag, peak) = valid_lags
+        .iter()
+        .map(|value| value.abs())
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+
+    let average = valid_lags.iter().map(|value| value.abs()).sum::<f64>() / valid_lags.len() as f64;
+    if average <= 0.0 {
+        return None;
+    }
+
+    Some(CorrelationPeak {
+        lag,
+        interpolated_lag: get_interpolated_peak_lag(valid_lags, lag),
+        is_inverted: valid_lags[lag] < 0.0,
+        peak_to_average_ratio: (peak / average) as f32,
+    })
+}
+
+fn get_interpolated_peak_lag(correlation: &[f64], lag: usize) -> f32 {
+    let (Some(previous), Some(next)) = (
+        lag.checked_sub(1).and_then(|index| correlation.get(index)),
+        correlation.get(lag + 1),
+    ) else {
+        return lag as f32;
+    };
+
+    let previous = previous.abs();
+    let peak = correlation[lag].abs();
+    let next = next.abs();
+    let curvature = previous - 2.0 * peak + next;
+
+    if curvature.abs() <= f64::EPSILON {
+        return lag as f32;
+    }
+
+    (lag as f64 + (0.5 * (previous - next) / curvature).clamp(-0.5, 0.5)) as f32
+}
+
+fn get_cross_correlation(signal: &[f32], reference: &[f32]) -> Vec<f64> {
+    let fft_size = (signal.len() + reference.len()).next_power_of_two();
+
+    let mut signal_real = get_zero_padded_samples(signal, fft_size);
+    let mut signal_imaginary = vec![0.0; fft_size];
+    transform_in_place(&mut signal_real, &mut signal_imaginary);
+
+    let mut reference_real = get_zero_padded_samples(reference, fft_size);
+    let mut reference_imaginary = vec![0.0; fft_size];
+    transform_in_place(&mut reference_real, &mut reference_imaginary);
+
+    let mut product_real = Vec::with_capacity(fft_size);
+    let mut product_imaginary = Vec::with_capacity(fft_size);
+    for bin in 0..fft_size {
+        product_real.push(
+            signal_real[bin] * reference_real[bin] + signal_imaginary[bin] * reference_imaginary[bin],
+        );
+        product_imaginary.push(
+            -(signal_imaginary[bin] * reference_real[bin] - signal_real[bin] * reference_imaginary[bin]),
+        );
+    }
+
+    transform_in_place(&mut product_real, &mut product_imaginary);
+
+    product_real
+        .iter()
+        .map(|value| value / fft_size as f64)
+        .collect()
+}
+
+fn get_zero_padded_samples(samples: &[f32], fft_size: usize) -> Vec<f64> {
+    let mut padded: Vec<f64> = samples.iter().map(|sample| f64::from(*sample)).collect();
+    padded.resize(fft_size, 0.0);
+    padded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_reference() -> Vec<f32> {
+        let mut state: u32 = 12345;
+        (0..255)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                if state & 0x4000_0000 == 0 { 1.0 } else { -1.0 }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn return_lag_of_delayed_reference_in_signal() {
+        let reference = get_test_reference();
+        let mut signal = vec![0.0; 1000];
+        signal[321..321 + reference.len()].copy_from_slice(&reference);
+
+        let peak = get_cross_correlation_peak(&signal, &reference).unwrap();
+        assert_eq!(peak.lag, 321);
+        assert!(!peak.is_inverted);
+        assert!((peak.interpolated_lag - 321.0).abs() < 0.5);
+        assert!(peak.peak_to_average_ratio > 5.0);
+    }
+
+    #[test]
+    fn return_lag_of_inverted_reference_in_signal() {
+        let reference = get_test_reference();
+        let mut signal = vec![0.0; 600];
+        for (index, sample) in reference.iter().enumerate() {
+            signal[40 + index] = -0.25 * sample;
+        }
+
+        let peak = get_cross_correlation_peak(&signal, &reference).unwrap();
+        assert_eq!(peak.lag, 40);
+        assert!(peak.is_inverted);
+    }
+
+    #[test]
+    fn return_no_peak_when_the_signal_is_shorter_than_the_reference() {
+        let reference = get_test_reference();
+        assert!(get_cross_correlation_peak(&reference[..100], &reference).is_none());
+        assert!(get_cross_correlation_peak(&vec![0.0; 600], &reference).is_none());
+    }
+}
@@ -0,0 +1,96 @@
+use std::f64::consts::PI;
+
+const DC_BLOCKING_CUTOFF_FREQUENCY: f64 = 2.0;
+
+pub fn get_dc_offset(samples: &[f32]) -> Option<f32> {
+    if samples.is_empty() {
+        return None;
+    }
+
+    let sum = samples.iter().map(|sample| f64::from(*sample)).sum::<f64>();
+    Some((sum / samples.len() as f64) as f32)
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DcBlockingFilter {
+    previous_input: f64,
+    previous_output: f64,
+    is_primed: bool,
+}
+
+impl DcBlockingFilter {
+    pub fn process(&mut self, samples: &[f32], sample_rate: f32) -> Vec<f32> {
+        if sample_rate <= 0.0 {
+            return samples.to_vec();
+        }
+
+        let pole = (-2.0 * PI * DC_BLOCKING_CUTOFF_FREQUENCY / f64::from(sample_rate)).exp();
+
+        samples
+            .iter()
+            .map(|sample| {
+                let input = f64::from(*sample);
+
+                if !self.is_primed {
+                    self.previous_input = input;
+                    self.is_primed = true;
+                }
+
+                let output = input - self.previous_input + pole * self.previous_output;
+                self.previous_input = input;
+                self.previous_output = output;
+                output as f32
+            })
+            .collect()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::generate_sine_samples;
+
+    fn get_sine_samples_with_offset(offset: f32, length: usize) -> Vec<f32> {
+        generate_sine_samples(100.0, 0.5, 0.0, 48000.0, length)
+            .iter()
+            .map(|sample| offset + sample)
+            .collect()
+    }
+
+    #[test]
+    fn return_dc_offset_of_sine_wave_with_offset() {
+        let samples = get_sine_samples_with_offset(0.01, 48000);
+        assert!((get_dc_offset(&samples).unwrap() - 0.01).abs() < 0.0001);
+        assert!(get_dc_offset(&[]).is_none());
+    }
+
+    #[test]
+    fn filter_removes_dc_without_changing_the_peak_of_a_low_frequency_tone() {
+        let samples = get_sine_samples_with_offset(0.05, 96000);
+        let mut filter = DcBlockingFilter::default();
+
+        let filtered = filter.process(&samples, 48000.0);
+        let settled = &filtered[48000..];
+
+        assert!(get_dc_offset(settled).unwrap().abs() < 0.0005);
+        let peak = settled.iter().fold(0.0f32, |peak, sample| sample.abs().max(peak));
+        assert!((peak - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn filter_state_carries_across_buffers() {
+        let samples = get_sine_samples_with_offset(0.05, 4096);
+        let mut whole_filter = DcBlockingFilter::default();
+        let mut split_filter = DcBlockingFilter::default();
+
+        let whole = whole_filter.process(&samples, 48000.0);
+        let mut split = split_filter.process(&samples[..1000], 48000.0);
+        split.extend(split_filter.process(&samples[1000..], 48000.0));
+
+        assert_eq!(whole, split);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::analysis::fft::PowerSpectrum;
+use crate::analysis::weighting::get_a_weighting_gain;
+
+const FUNDAMENTAL_NOTCH_HALF_WIDTH_IN_BINS: usize = 6;
+const MINIMUM_FUNDAMENTAL_POWER: f64 = 1e-12;
+const MINIMUM_RESIDUAL_POWER: f64 = 1e-20;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DistortionSettings {
+    pub low_frequency_limit: f32,
+    pub high_frequency_limit: f32,
+    pub a_weighting_enabled: bool,
+}
+
+impl Default for DistortionSettings {
+    fn default() -> Self {
+        Self {
+            low_frequency_limit: 20.0,
+            high_frequency_limit: 20000.0,
+            a_weighting_enabled: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DistortionMeasurement {
+    pub fundamental_frequency: f32,
+    pub thd_n_percent: f32,
+    pub thd_n_db: f32,
+    pub sinad_db: f32,
+}
+
+pub fn get_distortion_measurement(
+    spectrum: &PowerSpectrum,
+    settings: &DistortionSettings,
+) -> Option<DistortionMeasurement> {
+    let (low_bin, high_bin) = get_bin_range_from_bandwidth_limits(spectrum, settings);
+    if low_bin >= high_bin {
+        return None;
+    }
+
+    let fundamental_bin = spectrum.get_peak_bin(low_bin, high_bin)?;
+    let notch_low_bin = fundamental_bin.saturating_sub(FUNDAMENTAL_NOTCH_HALF_WIDTH_IN_BINS);
+    let notch_high_bin = fundamental_bin + FUNDAMENTAL_NOTCH_HALF_WIDTH_IN_BINS;
+
+    let mut fundamental_power = 0.0;
+    let mut residual_power = 0.0;
+
+    for bin in low_bin..=high_bin {
+        if (notch_low_bin..=notch_high_bin).contains(&bin) {
+            fundamental_power += spectrum.bins[bin];
+        } else {
+            let weighting = if settings.a_weighting_enabled {
+                get_a_weighting_gain(spectrum.get_bin_frequency(bin))
+            } else {
+                1.0
+            };
+            residual_power += spectrum.bins[bin] * weighting * weighting;
+        }
+    }
+
+    if fundamental_power < MINIMUM_FUNDAMENTAL_POWER {
+        return None;
+    }
+
+    let residual_power = residual_power.max(MINIMUM_RESIDUAL_POWER);
+    let thd_n_ratio = (residual_power / (fundamental_power + residual_power)).sqrt();
+
+    Some(DistortionMeasurement {
+        fundamental_frequency: spectrum.get_interpolated_peak_frequency(fundamental_bin),
+        thd_n_percent: (thd_n_ratio * 100.0) as f32,
+        thd_n_db: (20.0 * thd_n_ratio.log10()) as f32,
+        sinad_db: (-20.0 * thd_n_ratio.log10()) as f32,
+    })
+}
+
+fn get_bin_range_from_bandwidth_limits(
+    spectrum: &PowerSpectrum,
+    settings: &DistortionSettings,
+) -> (usize, usize) {
+    let low_bin = spectrum.get_bin_from_frequency(settings.low_frequency_limit).max(1);
+    let high_bin = spectrum.get_bin_from_frequency(settings.high_frequency_limit);
+    (low_bin, high_bin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::fft::get_block_power_spectrum;
+    use crate::analysis::generate_sine_samples;
+
+    fn generate_distorted_sine_spectrum(harmonic: f32, harmonic_amplitude: f32) -> PowerSpectrum {
+        let fundamental = generate_sine_samples(1000.0, 0.5, 0.0, 48000.0, 10240);
+        let distortion = generate_sine_samples(harmonic * 1000.0, harmonic_amplitude, 0.0, 48000.0, 10240);
+        let samples: Vec<f32> = fundamental.iter().zip(&distortion).map(|(fundamental, distortion)| fundamental + distortion).collect();
+        get_block_power_spectrum(&samples, 48000.0).unwrap()
+    }
+
+    #[test]
+    fn return_correct_thd_n_of_sine_wave_with_one_percent_third_harmonic() {
+        let spectrum = generate_distorted_sine_spectrum(3.0, 0.005);
+        let measurement =
+            get_distortion_measurement(&spectrum, &DistortionSettings::default()).unwrap();
+        assert!((measurement.thd_n_percent - 1.0).abs() < 0.01);
+        assert!((measurement.thd_n_db + 40.0).abs() < 0.1);
+        assert!((measurement.sinad_db - 40.0).abs() < 0.1);
+        assert!((measurement.fundamental_frequency - 1000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn harmonics_outside_the_bandwidth_limits_are_ignored() {
+        let spectrum = generate_distorted_sine_spectrum(3.0, 0.005);
+        let settings = DistortionSettings {
+            high_frequency_limit: 2000.0,
+            ..DistortionSettings::default()
+        };
+        let measurement = get_distortion_measurement(&spectrum, &settings).unwrap();
+        assert!(measurement.thd_n_percent < 0.01);
+    }
+
+    #[test]
+    fn a_weighting_lowers_residual_of_high_frequency_harmonic() {
+        let spectrum = generate_distorted_sine_spectrum(15.0, 0.005);
+        let settings = DistortionSettings {
+            a_weighting_enabled: true,
+            ..DistortionSettings::default()
+        };
+        let unweighted =
+            get_distortion_measurement(&spectrum, &DistortionSettings::default()).unwrap();
+        let weighted = get_distortion_measurement(&spectrum, &settings).unwrap();
+        assert!(weighted.thd_n_percent < unweighted.thd_n_percent);
+    }
+
+    #[test]
+    fn return_no_measurement_for_silence() {
+        let spectrum = get_block_power_spectrum(&[0.0; 4096], 48000.0).unwrap();
+        assert!(get_distortion_measurement(&spectrum, &DistortionSettings::default()).is_none());
+    }
+}
@@ -0,0 +1,323 @@
+use crate::analysis::get_hann_window_value;
+use std::f64::consts::PI;
+
+const BLACKMAN_HARRIS_COEFFICIENTS: [f64; 4] = [0.35875, 0.48829, 0.14128, 0.01168];
+const FLAT_TOP_COEFFICIENTS: [f64; 5] = [0.215_578_95, 0.416_631_58, 0.277_263_158, 0.083_578_947, 0.006_947_368];
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum WindowFunction {
+    Rectangular,
+    Hann,
+    #[default]
+    BlackmanHarris,
+    FlatTop,
+}
+
+impl WindowFunction {
+    pub fn get_value(&self, index: usize, window_length: usize) -> f64 {
+        match self {
+            WindowFunction::Rectangular => 1.0,
+            WindowFunction::Hann => get_hann_window_value(index, window_length),
+            WindowFunction::BlackmanHarris => {
+                get_cosine_sum_window_value(&BLACKMAN_HARRIS_COEFFICIENTS, index, window_length)
+            }
+            WindowFunction::FlatTop => {
+                get_cosine_sum_window_value(&FLAT_TOP_COEFFICIENTS, index, window_length)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PowerSpectrum {
+    pub bins: Vec<f64>,
+    pub bin_width: f32,
+    pub window_function: WindowFunction,
+    equivalent_noise_bandwidth_in_bins: f64,
+}
+
+impl PowerSpectrum {
+    pub fn get_bin_frequency(&self, bin: usize) -> f32 {
+        bin as f32 * self.bin_width
+    }
+
+    pub fn get_bin_from_frequency(&self, frequency: f32) -> usize {
+        ((frequency / self.bin_width).round().max(0.0) as usize).min(self.bins.len().saturating_sub(1))
+    }
+
+    pub fn get_peak_bin(&self, low_bin: usize, high_bin: usize) -> Option<usize> {
+        (low_bin..=high_bin.min(self.bins.len().saturating_sub(1)))
+            .max_by(|a, b| self.bins[*a].total_cmp(&self.bins[*b]))
+    }
+
+    pub fn get_tone_power(&self, frequency: f32, half_width_in_bins: usize) -> f64 {
+        let centre_bin = self.get_bin_from_frequency(frequency);
+        let low_bin = centre_bin.saturating_sub(half_width_in_bins);
+        let high_bin = (centre_bin + half_width_in_bins).min(self.bins.len().saturating_sub(1));
+        self.bins[low_bin..=high_bin].iter().sum()
+    }
+
+    pub fn get_amplitudes(&self) -> Vec<f64> {
+        let last_bin = self.bins.len().saturating_sub(1);
+        self.bins
+            .iter()
+            .enumerate()
+            .map(|(bin, power)| {
+                let power = if bin == 0 || bin == last_bin { 2.0 * power } else { *power };
+                (2.0 * power * self.equivalent_noise_bandwidth_in_bins).sqrt()
+            })
+            .collect()
+    }
+
+    pub fn get_interpolated_peak_frequency(&self, peak_bin: usize) -> f32 {
+        let (Some(previous), Some(next)) = (
+            peak_bin.checked_sub(1).and_then(|bin| self.bins.get(bin)),
+            self.bins.get(peak_bin + 1),
+        ) else {
+            return self.get_bin_frequency(peak_bin);
+        };
+
+        let previous = previous.max(f64::MIN_POSITIVE).ln();
+        let peak = self.bins[peak_bin].max(f64::MIN_POSITIVE).ln();
+        let next = next.max(f64::MIN_POSITIVE).ln();
+        let curvature = previous - 2.0 * peak + next;
+
+        let offset = if curvature.abs() > f64::EPSILON {
+            (0.5 * (previous - next) / curvature).clamp(-0.5, 0.5)
+        } else {
+            0.0
+        };
+
+        (peak_bin as f64 + offset) as f32 * self.bin_width
+    }
+}
+
+pub fn get_largest_fft_size(number_of_samples: usize) -> usize {
+    if number_of_samples == 0 {
+        return 0;
+    }
+    1 << (usize::BITS - 1 - number_of_samples.leading_zeros())
+}
+
+pub fn get_block_power_spectrum(samples: &[f32], sample_rate: f32) -> Option<PowerSpectrum> {
+    get_power_spectrum(samples, get_largest_fft_size(samples.len()), sample_rate)
+}
+
+pub fn get_power_spectrum(
+    samples: &[f32],
+    fft_size: usize,
+    sample_rate: f32,
+) -> Option<PowerSpectrum> {
+    if fft_size < 2 || !fft_size.is_power_of_two() || samples.len() < fft_size || sample_rate <= 0.0
+    {
+        return None;
+    }
+
+    let window_function = WindowFunction::BlackmanHarris;
+    let latest_samples = &samples[samples.len() - fft_size..];
+    let mut window_sum = 0.0;
+    let mut window_power_sum = 0.0;
+    let mut real: Vec<f64> = Vec::with_capacity(fft_size);
+
+    for (index, sample) in latest_samples.iter().enumerate() {
+        let window = window_function.get_value(index, fft_size);
+        window_sum += window;
+        window_power_sum += window * window;
+        real.push(f64::from(*sample) * window);
+    }
+
+    let mut imaginary = vec![0.0; fft_size];
+    transform_in_place(&mut real, &mut imaginary);
+
+    let normalization = 2.0 / (fft_size as f64 * window_power_sum);
+    let bins = (0..=fft_size / 2)
+        .map(|bin| {
+            let power = (real[bin] * real[bin] + imaginary[bin] * imaginary[bin]) * normalization;
+            if bin == 0 || bin == fft_size / 2 {
+                power / 2.0
+            } else {
+                power
+            }
+        })
+        .collect();
+
+    Some(PowerSpectrum {
+        bins,
+        bin_width: sample_rate / fft_size as f32,
+        window_function,
+        equivalent_noise_bandwidth_in_bins: fft_size as f64 * window_power_sum
+            / (window_sum * window_sum).max(f64::MIN_POSITIVE),
+    })
+}
+
+pub fn get_amplitude_spectrum(
+    samples: &[f32],
+    fft_size: usize,
+    window_function: WindowFunction,
+) -> Option<Vec<f64>> {
+    if fft_size < 2 || !fft_size.is_power_of_two() || samples.len() < fft_size {
+        return None;
+    }
+
+    let latest_samples = &samples[samples.len() - fft_size..];
+    let mut window_sum = 0.0;
+    let mut real: Vec<f64> = Vec::with_capacity(fft_size);
+
+    for (index, sample) in latest_samples.iter().enumerate() {
+        let window = window_function.get_value(index, fft_size);
+        window_sum += window;
+        real.push(f64::from(*sample) * window);
+    }
+
+    let mut imaginary = vec![0.0; fft_size];
+    transform_in_place(&mut real, &mut imaginary);
+
+    let normalization = 2.0 / window_sum.max(f64::MIN_POSITIVE);
+    Some(
+        (0..=fft_size / 2)
+            .map(|bin| (real[bin] * real[bin] + imaginary[bin] * imaginary[bin]).sqrt() * normalization)
+            .collect(),
+    )
+}
+
+fn get_cosine_sum_window_value(coefficients: &[f64], index: usize, window_length: usize) -> f64 {
+    if window_length < 2 {
+        return 1.0;
+    }
+
+    let phase = 2.0 * PI * index as f64 / (window_length - 1) as f64;
+
+    coefficients
+        .iter()
+        .enumerate()
+        .map(|(order, coefficient)| {
+            let sign = if order % 2 == 0 { 1.0 } else { -1.0 };
+            sign * coefficient * (order as f64 * phase).cos()
+        })
+        .sum()
+}
+
+pub fn transform_in_place(real: &mut [f64], imaginary: &mut [f64]) {
+    let length = real.len();
+
+    let mut reversed_index = 0;
+    for index in 1..length {
+        let mut bit = length >> 1;
+        while reversed_index & bit != 0 {
+            reversed_index ^= bit;
+            bit >>= 1;
+        }
+        reversed_index |= bit;
+
+        if index < reversed_index {
+            real.swap(index, reversed_index);
+            imaginary.swap(index, reversed_index);
+        }
+    }
+
+    let mut span = 2;
+    while span <= length {
+        let angle = -2.0 * PI / span as f64;
+        let (step_imaginary, step_real) = angle.sin_cos();
+
+        for start in (0..length).step_by(span) {
+            let mut twiddle_real = 1.0;
+            let mut twiddle_imaginary = 0.0;
+
+            for offset in 0..span / 2 {
+                let even = start + offset;
+                let odd = even + span / 2;
+
+                let odd_real = real[odd] * twiddle_real - imaginary[odd] * twiddle_imaginary;
+                let odd_imaginary = real[odd] * twiddle_imaginary + imaginary[odd] * twiddle_real;
+
+                real[odd] = real[even] - odd_real;
+                imaginary[odd] = imaginary[even] - odd_imaginary;
+                real[even] += odd_real;
+                imaginary[even] += odd_imaginary;
+
+                let next_twiddle_real = twiddle_real * step_real - twiddle_imaginary * step_imaginary;
+                twiddle_imaginary = twiddle_real * step_imaginary + twiddle_imaginary * step_real;
+                twiddle_real = next_twiddle_real;
+            }
+        }
+
+        span <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::generate_sine_samples;
+
+    #[test]
+    fn return_largest_power_of_two_fft_size_that_fits_the_samples() {
+        assert_eq!(get_largest_fft_size(10240), 8192);
+        assert_eq!(get_largest_fft_size(4096), 4096);
+        assert_eq!(get_largest_fft_size(0), 0);
+    }
+
+    #[test]
+    fn return_spectrum_peak_at_the_sine_wave_frequency() {
+        let samples = generate_sine_samples(1000.0, 0.5, 0.0, 48000.0, 8192);
+        let spectrum = get_power_spectrum(&samples, 8192, 48000.0).unwrap();
+        let peak_bin = spectrum
+            .bins
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(bin, _)| bin)
+            .unwrap();
+        assert_eq!(peak_bin, spectrum.get_bin_from_frequency(1000.0));
+    }
+
+    #[test]
+    fn total_spectrum_power_matches_the_sine_wave_mean_square() {
+        let samples = generate_sine_samples(1000.0, 0.5, 0.0, 48000.0, 8192);
+        let spectrum = get_power_spectrum(&samples, 8192, 48000.0).unwrap();
+        let total_power: f64 = spectrum.bins.iter().sum();
+        assert!((total_power - 0.125).abs() < 0.001);
+    }
+
+    #[test]
+    fn amplitude_spectrum_peak_matches_the_sine_wave_amplitude_for_every_window() {
+        let samples = generate_sine_samples(1500.0, 0.5, 0.0, 48000.0, 4096);
+        let peak_bin = (1500.0_f32 / (48000.0 / 4096.0)).round() as usize;
+
+        for window_function in [
+            WindowFunction::Rectangular,
+            WindowFunction::Hann,
+            WindowFunction::BlackmanHarris,
+            WindowFunction::FlatTop,
+        ] {
+            let spectrum = get_amplitude_spectrum(&samples, 4096, window_function).unwrap();
+            assert_eq!(spectrum.len(), 2049);
+            assert!(
+                (spectrum[peak_bin] - 0.5).abs() < 0.01,
+                "{window_function:?} read {}",
+                spectrum[peak_bin]
+            );
+        }
+    }
+
+    #[test]
+    fn power_spectrum_amplitudes_match_the_amplitude_spectrum_of_the_same_window() {
+        let samples = generate_sine_samples(1500.0, 0.5, 0.0, 48000.0, 10240);
+        let power_spectrum = get_block_power_spectrum(&samples, 48000.0).unwrap();
+        let amplitude_spectrum =
+            get_amplitude_spectrum(&samples, 8192, power_spectrum.window_function).unwrap();
+
+        for (from_power, amplitude) in power_spectrum.get_amplitudes().iter().zip(&amplitude_spectrum) {
+            assert!((from_power - amplitude).abs() < 1e-9);
+        }
+        assert!((power_spectrum.get_tone_power(1500.0, 4) - 0.125).abs() < 0.001);
+    }
+
+    #[test]
+    fn return_no_spectrum_when_there_are_not_enough_samples() {
+        let samples = [0.0; 100];
+        assert!(get_power_spectrum(&samples, 128, 48000.0).is_none());
+        assert!(get_power_spectrum(&samples, 96, 48000.0).is_none());
+    }
+}
@@ -0,0 +1,52 @@
+use crate::analysis::fft::PowerSpectrum;
+
+const FREQUENCY_SEARCH_LOW_LIMIT: f32 = 20.0;
+const FREQUENCY_SEARCH_HIGH_LIMIT: f32 = 20000.0;
+const MINIMUM_PEAK_POWER: f64 = 1e-8;
+const FREQUENCY_MATCH_TOLERANCE_RATIO: f32 = 0.01;
+const MINIMUM_FREQUENCY_MATCH_TOLERANCE: f32 = 1.0;
+
+pub fn get_dominant_frequency(spectrum: &PowerSpectrum) -> Option<f32> {
+    let low_bin = spectrum.get_bin_from_frequency(FREQUENCY_SEARCH_LOW_LIMIT).max(1);
+    let high_bin = spectrum.get_bin_from_frequency(FREQUENCY_SEARCH_HIGH_LIMIT);
+    let peak_bin = spectrum.get_peak_bin(low_bin, high_bin)?;
+
+    if spectrum.bins[peak_bin] < MINIMUM_PEAK_POWER {
+        return None;
+    }
+
+    Some(spectrum.get_interpolated_peak_frequency(peak_bin))
+}
+
+pub fn is_frequency_matching_reference(measured_frequency: f32, reference_frequency: f32) -> bool {
+    let tolerance = (reference_frequency * FREQUENCY_MATCH_TOLERANCE_RATIO)
+        .max(MINIMUM_FREQUENCY_MATCH_TOLERANCE);
+    (measured_frequency - reference_frequency).abs() <= tolerance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::generate_sine_spectrum;
+
+    #[test]
+    fn return_correct_frequency_of_sine_wave_between_bins() {
+        for frequency in [100.0, 997.0, 1000.0, 12345.0] {
+            let measured = get_dominant_frequency(&generate_sine_spectrum(frequency, 0.5, 48000.0, 10240)).unwrap();
+            assert!((measured - frequency).abs() < 0.5, "{frequency} measured as {measured}");
+        }
+    }
+
+    #[test]
+    fn return_none_for_silence() {
+        assert!(get_dominant_frequency(&generate_sine_spectrum(1000.0, 0.0, 48000.0, 10240)).is_none());
+    }
+
+    #[test]
+    fn frequency_matches_reference_only_within_tolerance() {
+        assert!(is_frequency_matching_reference(1000.4, 1000.0));
+        assert!(is_frequency_matching_reference(99.2, 100.0));
+        assert!(!is_frequency_matching_reference(1100.0, 1000.0));
+        assert!(!is_frequency_matching_reference(50.0, 1000.0));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::analysis::fft::PowerSpectrum;
+
+const MAINS_FREQUENCIES: [f32; 2] = [50.0, 60.0];
+const NUMBER_OF_MAINS_HARMONICS: usize = 5;
+const MINIMUM_HARMONIC_DISTANCE_FROM_REFERENCE: f32 = 15.0;
+const MINIMUM_HARMONIC_DISTANCE_FROM_REFERENCE_IN_BINS: f32 = 8.0;
+const TONE_HALF_WIDTH_IN_BINS: usize = 3;
+const MINIMUM_REFERENCE_POWER: f64 = 5e-9;
+const HUM_WARNING_THRESHOLD_DB: f32 = -60.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HumMeasurement {
+    pub mains_frequency: f32,
+    pub relative_level_db: f32,
+}
+
+impl HumMeasurement {
+    pub fn is_above_warning_threshold(&self) -> bool {
+        self.relative_level_db > HUM_WARNING_THRESHOLD_DB
+    }
+}
+
+pub fn get_hum_measurement(
+    spectrum: &PowerSpectrum,
+    reference_frequency: f32,
+) -> Option<HumMeasurement> {
+    if reference_frequency <= 0.0 {
+        return None;
+    }
+
+    let reference_power = spectrum.get_tone_power(reference_frequency, TONE_HALF_WIDTH_IN_BINS);
+    if reference_power < MINIMUM_REFERENCE_POWER {
+        return None;
+    }
+
+    let minimum_distance = get_minimum_harmonic_distance(spectrum.bin_width);
+    let (mains_frequency, hum_power) = MAINS_FREQUENCIES
+        .iter()
+        .map(|mains_frequency| {
+            let hum_power = get_mains_harmonic_frequencies(*mains_frequency, reference_frequency, minimum_distance)
+                .map(|frequency| spectrum.get_tone_power(frequency, TONE_HALF_WIDTH_IN_BINS))
+                .sum::<f64>();
+            (*mains_frequency, hum_power)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+
+    if hum_power <= 0.0 {
+        return None;
+    }
+
+    Some(HumMeasurement {
+        mains_frequency,
+        relative_level_db: (10.0 * (hum_power / reference_power).log10()) as f32,
+    })
+}
+
+fn get_minimum_harmonic_distance(bin_width: f32) -> f32 {
+    (MINIMUM_HARMONIC_DISTANCE_FROM_REFERENCE_IN_BINS * bin_width).max(MINIMUM_HARMONIC_DISTANCE_FROM_REFERENCE)
+}
+
+fn get_mains_harmonic_frequencies(
+    mains_frequency: f32,
+    reference_frequency: f32,
+    minimum_distance: f32,
+) -> impl Iterator<Item = f32> {
+    (1..=NUMBER_OF_MAINS_HARMONICS)
+        .map(move |harmonic| mains_frequency * harmonic as f32)
+        .filter(move |frequency| (frequency - reference_frequency).abs() >= minimum_distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::fft::get_block_power_spectrum;
+    use crate::analysis::{generate_sine_samples, generate_sine_spectrum};
+
+    fn get_tone_with_hum_spectrum(mains_frequency: f32, hum_amplitude: f32) -> PowerSpectrum {
+        let tone = generate_sine_samples(1000.0, 0.5, 0.0, 48000.0, 10240);
+        let hum = generate_sine_samples(mains_frequency, hum_amplitude, 0.0, 48000.0, 10240);
+        let third_harmonic =
+            generate_sine_samples(3.0 * mains_frequency, 0.5 * hum_amplitude, 0.0, 48000.0, 10240);
+        let samples: Vec<f32> = tone
+            .iter()
+            .zip(&hum)
+            .zip(&third_harmonic)
+            .map(|((tone, hum), third_harmonic)| tone + hum + third_harmonic)
+            .collect();
+        get_block_power_spectrum(&samples, 48000.0).unwrap()
+    }
+
+    #[test]
+    fn return_hum_level_relative_to_the_reference_tone() {
+        let spectrum = get_tone_with_hum_spectrum(50.0, 0.005);
+        let hum = get_hum_measurement(&spectrum, 1000.0).unwrap();
+        let expected_db = 10.0 * ((0.005_f32.powi(2) + 0.0025_f32.powi(2)) / 0.25).log10();
+
+        assert_eq!(hum.mains_frequency, 50.0);
+        assert!((hum.relative_level_db - expected_db).abs() < 0.5);
+        assert!(hum.is_above_warning_threshold());
+    }
+
+    #[test]
+    fn identify_sixty_hertz_mains_hum() {
+        let spectrum = get_tone_with_hum_spectrum(60.0, 0.005);
+        let hum = get_hum_measurement(&spectrum, 1000.0).unwrap();
+        assert_eq!(hum.mains_frequency, 60.0);
+    }
+
+    #[test]
+    fn clean_reference_tone_does_not_raise_a_warning() {
+        let spectrum = get_tone_with_hum_spectrum(50.0, 0.0);
+        let hum = get_hum_measurement(&spectrum, 1000.0);
+        assert!(hum.is_none_or(|hum| !hum.is_above_warning_threshold()));
+    }
+
+    #[test]
+    fn return_none_without_a_reference_tone() {
+        let spectrum = get_block_power_spectrum(&[0.0; 10240], 48000.0).unwrap();
+        assert!(get_hum_measurement(&spectrum, 1000.0).is_none());
+    }
+
+    #[test]
+    fn clean_low_frequency_reference_tone_does_not_raise_a_warning() {
+        for (frequency, length) in [(100.0, 10240), (165.0, 10240), (100.0, 4096)] {
+            let spectrum = generate_sine_spectrum(frequency, 0.5, 48000.0, length);
+            let hum = get_hum_measurement(&spectrum, frequency);
+            assert!(hum.is_none_or(|hum| !hum.is_above_warning_threshold()));
+        }
+    }
+
+    #[test]
+    fn skip_mains_harmonics_close_to_the_reference_frequency() {
+        let harmonics: Vec<f32> = get_mains_harmonic_frequencies(50.0, 100.0, 15.0).collect();
+        assert_eq!(harmonics, vec![50.0, 150.0, 200.0, 250.0]);
+    }
+
+    #[test]
+    fn widen_the_harmonic_exclusion_for_short_blocks() {
+        let minimum_distance = get_minimum_harmonic_distance(48000.0 / 4096.0);
+        let harmonics: Vec<f32> = get_mains_harmonic_frequencies(50.0, 100.0, minimum_distance).collect();
+        assert_eq!(harmonics, vec![200.0, 250.0]);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::analysis::fft::{get_largest_fft_size, get_power_spectrum};
+use crate::analysis::weighting::get_a_weighting_gain;
+
+const NOISE_BANDWIDTH_LOW_FREQUENCY: f32 = 20.0;
+const NOISE_BANDWIDTH_HIGH_FREQUENCY: f32 = 20000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct NoisePower {
+    pub unweighted: f64,
+    pub a_weighted: f64,
+}
+
+impl NoisePower {
+    pub fn add(&mut self, other: &NoisePower) {
+        self.unweighted += other.unweighted;
+        self.a_weighted += other.a_weighted;
+    }
+
+    pub fn get_average(&self, number_of_measurements: usize) -> NoisePower {
+        let count = number_of_measurements.max(1) as f64;
+        NoisePower {
+            unweighted: self.unweighted / count,
+            a_weighted: self.a_weighted / count,
+        }
+    }
+}
+
+pub fn get_noise_power(samples: &[f32], sample_rate: f32) -> Option<NoisePower> {
+    let fft_size = get_largest_fft_size(samples.len());
+    let spectrum = get_power_spectrum(&get_samples_without_dc_offset(samples), fft_size, sample_rate)?;
+
+    let low_bin = spectrum.get_bin_from_frequency(NOISE_BANDWIDTH_LOW_FREQUENCY).max(1);
+    let high_bin = spectrum.get_bin_from_frequency(NOISE_BANDWIDTH_HIGH_FREQUENCY);
+
+    let mut noise_power = NoisePower::default();
+
+    for bin in low_bin..=high_bin {
+        let weighting = get_a_weighting_gain(spectrum.get_bin_frequency(bin));
+        noise_power.unweighted += spectrum.bins[bin];
+        noise_power.a_weighted += spectrum.bins[bin] * weighting * weighting;
+    }
+
+    Some(noise_power)
+}
+
+fn get_samples_without_dc_offset(samples: &[f32]) -> Vec<f32> {
+    if samples.is_empty() {
+        return Vec::new();
+    }
+
+    let dc_offset = samples.iter().map(|sample| f64::from(*sample)).sum::<f64>() / samples.len() as f64;
+    samples
+        .iter()
+        .map(|sample| (f64::from(*sample) - dc_offset) as f32)
+        .collect()
+}
+
+pub fn get_dbfs_from_mean_square(mean_square: f64) -> f32 {
+    (10.0 * (2.0 * mean_square).log10()) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::generate_sine_samples;
+
+    #[test]
+    fn return_full_scale_sine_mean_square_as_zero_dbfs() {
+        assert!(get_dbfs_from_mean_square(0.5).abs() < 1e-6);
+        assert_eq!(get_dbfs_from_mean_square(0.0), f32::NEG_INFINITY);
+    }
+
+    #[test]
+    fn return_noise_power_matching_the_level_of_an_in_band_tone() {
+        let samples = generate_sine_samples(1000.0, 0.01, 0.0, 48000.0, 10240);
+        let noise_power = get_noise_power(&samples, 48000.0).unwrap();
+        assert!((get_dbfs_from_mean_square(noise_power.unweighted) + 40.0).abs() < 0.05);
+        assert!((get_dbfs_from_mean_square(noise_power.a_weighted) + 40.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn out_of_band_content_is_not_counted_as_noise() {
+        let samples = vec![0.25; 10240];
+        let noise_power = get_noise_power(&samples, 48000.0).unwrap();
+        assert!(get_dbfs_from_mean_square(noise_power.unweighted) < -100.0);
+    }
+
+    #[test]
+    fn return_average_noise_power_of_accumulated_measurements() {
+        let mut noise_power = NoisePower::default();
+        noise_power.add(&NoisePower { unweighted: 1.0, a_weighted: 0.5 });
+        noise_power.add(&NoisePower { unweighted: 3.0, a_weighted: 1.5 });
+        assert_eq!(
+            noise_power.get_average(2),
+            NoisePower { unweighted: 2.0, a_weighted: 1.0 }
+        );
+    }
+}
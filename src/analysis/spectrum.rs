@@ -0,0 +1,198 @@
+use crate::analysis::fft::{PowerSpectrum, WindowFunction, get_amplitude_spectrum};
+
+pub const SPECTRUM_FFT_SIZES: [usize; 6] = [1024, 2048, 4096, 8192, 16384, 32768];
+const MINIMUM_SPECTRUM_AMPLITUDE: f64 = 1e-10;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectrumSettings {
+    pub fft_size: usize,
+    pub window_function: WindowFunction,
+    pub averages: usize,
+}
+
+impl Default for SpectrumSettings {
+    fn default() -> Self {
+        Self {
+            fft_size: 8192,
+            window_function: WindowFunction::default(),
+            averages: 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Spectrum {
+    pub bin_width: f32,
+    pub left: Vec<f32>,
+    pub right: Vec<f32>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SpectrumAnalyzer {
+    settings: SpectrumSettings,
+    sample_rate: f32,
+    left_history: Vec<f32>,
+    right_history: Vec<f32>,
+    left_average: Vec<f64>,
+    right_average: Vec<f64>,
+}
+
+impl SpectrumAnalyzer {
+    pub fn update(
+        &mut self,
+        left_samples: &[f32],
+        right_samples: &[f32],
+        block_spectra: (Option<&PowerSpectrum>, Option<&PowerSpectrum>),
+        sample_rate: f32,
+        settings: &SpectrumSettings,
+    ) -> Option<Spectrum> {
+        if self.settings != *settings || self.sample_rate != sample_rate {
+            self.reset();
+            self.settings = *settings;
+            self.sample_rate = sample_rate;
+        }
+
+        if sample_rate <= 0.0 {
+            return None;
+        }
+
+        let fft_size = settings.fft_size;
+        add_samples_to_history(&mut self.left_history, left_samples, fft_size);
+        add_samples_to_history(&mut self.right_history, right_samples, fft_size);
+
+        let (left_block_spectrum, right_block_spectrum) = block_spectra;
+        let left = get_amplitudes(&self.left_history, left_block_spectrum, settings)?;
+        let right = get_amplitudes(&self.right_history, right_block_spectrum, settings);
+
+        update_average(&mut self.left_average, &left, settings.averages);
+        match right {
+            Some(right) => update_average(&mut self.right_average, &right, settings.averages),
+            None => self.right_average.clear(),
+        }
+
+        Some(Spectrum {
+            bin_width: sample_rate / fft_size as f32,
+            left: get_dbfs_levels_from_amplitudes(&self.left_average),
+            right: get_dbfs_levels_from_amplitudes(&self.right_average),
+        })
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+fn add_samples_to_history(history: &mut Vec<f32>, samples: &[f32], length: usize) {
+    history.extend_from_slice(samples);
+    if history.len() > length {
+        history.drain(..history.len() - length);
+    }
+}
+
+fn get_amplitudes(
+    history: &[f32],
+    block_spectrum: Option<&PowerSpectrum>,
+    settings: &SpectrumSettings,
+) -> Option<Vec<f64>> {
+    match block_spectrum {
+        Some(spectrum)
+            if spectrum.window_function == settings.window_function
+                && spectrum.bins.len() == settings.fft_size / 2 + 1 =>
+        {
+            Some(spectrum.get_amplitudes())
+        }
+        _ => get_amplitude_spectrum(history, settings.fft_size, settings.window_function),
+    }
+}
+
+fn update_average(average: &mut Vec<f64>, amplitudes: &[f64], averages: usize) {
+    if average.len() != amplitudes.len() {
+        *average = amplitudes.iter().map(|amplitude| amplitude * amplitude).collect();
+        return;
+    }
+
+    let smoothing = 1.0 / averages.max(1) as f64;
+    for (averaged_power, amplitude) in average.iter_mut().zip(amplitudes) {
+        *averaged_power += smoothing * (amplitude * amplitude - *averaged_power);
+    }
+}
+
+fn get_dbfs_levels_from_amplitudes(average_power: &[f64]) -> Vec<f32> {
+    average_power
+        .iter()
+        .map(|power| (20.0 * power.sqrt().max(MINIMUM_SPECTRUM_AMPLITUDE).log10()) as f32)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::fft::get_block_power_spectrum;
+    use crate::analysis::generate_sine_samples;
+
+    #[test]
+    fn return_no_spectrum_until_enough_samples_have_been_collected() {
+        let mut analyzer = SpectrumAnalyzer::default();
+        let settings = SpectrumSettings::default();
+        let samples = generate_sine_samples(1000.0, 0.5, 0.0, 48000.0, 5000);
+
+        assert!(analyzer.update(&samples, &samples, (None, None), 48000.0, &settings).is_none());
+
+        let spectrum = analyzer.update(&samples, &samples, (None, None), 48000.0, &settings).unwrap();
+        assert_eq!(spectrum.left.len(), 4097);
+        assert_eq!(spectrum.right.len(), 4097);
+    }
+
+    #[test]
+    fn return_sine_wave_level_in_dbfs_at_its_frequency() {
+        let mut analyzer = SpectrumAnalyzer::default();
+        let settings = SpectrumSettings {
+            fft_size: 4096,
+            window_function: WindowFunction::FlatTop,
+            averages: 1,
+        };
+        let samples = generate_sine_samples(1000.0, 0.5, 0.0, 48000.0, 4096);
+
+        let spectrum = analyzer.update(&samples, &[], (None, None), 48000.0, &settings).unwrap();
+        let bin = (1000.0 / spectrum.bin_width).round() as usize;
+
+        assert!((spectrum.left[bin] + 6.02).abs() < 0.1);
+        assert!(spectrum.right.is_empty());
+    }
+
+    #[test]
+    fn averaging_smooths_level_changes_between_blocks() {
+        let mut analyzer = SpectrumAnalyzer::default();
+        let settings = SpectrumSettings {
+            fft_size: 4096,
+            window_function: WindowFunction::FlatTop,
+            averages: 4,
+        };
+        let loud = generate_sine_samples(1000.0, 0.5, 0.0, 48000.0, 4096);
+        let silent = vec![0.0; 4096];
+
+        analyzer.update(&loud, &loud, (None, None), 48000.0, &settings).unwrap();
+        let spectrum = analyzer.update(&silent, &silent, (None, None), 48000.0, &settings).unwrap();
+        let bin = (1000.0 / spectrum.bin_width).round() as usize;
+
+        assert!((spectrum.left[bin] + 6.02 - 20.0 * 0.75_f32.sqrt().log10()).abs() < 0.1);
+    }
+
+    #[test]
+    fn reuse_a_matching_block_spectrum_instead_of_transforming_the_history_again() {
+        let settings = SpectrumSettings::default();
+        let samples = generate_sine_samples(1000.0, 0.5, 0.0, 48000.0, 10240);
+        let block_spectrum = get_block_power_spectrum(&samples, 48000.0).unwrap();
+
+        let transformed = SpectrumAnalyzer::default()
+            .update(&samples, &samples, (None, None), 48000.0, &settings)
+            .unwrap();
+        let reused = SpectrumAnalyzer::default()
+            .update(&samples, &samples, (Some(&block_spectrum), Some(&block_spectrum)), 48000.0, &settings)
+            .unwrap();
+
+        for (transformed_level, reused_level) in transformed.left.iter().zip(&reused.left) {
+            assert!((transformed_level - reused_level).abs() < 1e-3);
+        }
+    }
+}
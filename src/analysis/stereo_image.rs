@@ -0,0 +1,136 @@
+const STEREO_IMAGE_SMOOTHING_FACTOR: f64 = 0.3;
+const MINIMUM_CHANNEL_MEAN_SQUARE: f64 = 1e-10;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StereoImage {
+    pub correlation: f32,
+    pub balance_db: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StereoImageMeter {
+    left_mean_square: f64,
+    right_mean_square: f64,
+    cross_mean: f64,
+    has_history: bool,
+}
+
+impl StereoImageMeter {
+    pub fn update(&mut self, left_samples: &[f32], right_samples: &[f32]) -> Option<StereoImage> {
+        let number_of_samples = left_samples.len().min(right_samples.len());
+        if number_of_samples == 0 {
+            self.reset();
+            return None;
+        }
+
+        let mut left_sum = 0.0;
+        let mut right_sum = 0.0;
+        let mut cross_sum = 0.0;
+
+        for (left, right) in left_samples.iter().zip(right_samples.iter()) {
+            let left = f64::from(*left);
+            let right = f64::from(*right);
+            left_sum += left * left;
+            right_sum += right * right;
+            cross_sum += left * right;
+        }
+
+        let count = number_of_samples as f64;
+        let smoothing = if self.has_history {
+            STEREO_IMAGE_SMOOTHING_FACTOR
+        } else {
+            1.0
+        };
+
+        self.left_mean_square += smoothing * (left_sum / count - self.left_mean_square);
+        self.right_mean_square += smoothing * (right_sum / count - self.right_mean_square);
+        self.cross_mean += smoothing * (cross_sum / count - self.cross_mean);
+        self.has_history = true;
+
+        get_stereo_image_from_mean_squares(
+            self.left_mean_square,
+            self.right_mean_square,
+            self.cross_mean,
+        )
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+fn get_stereo_image_from_mean_squares(
+    left_mean_square: f64,
+    right_mean_square: f64,
+    cross_mean: f64,
+) -> Option<StereoImage> {
+    if left_mean_square < MINIMUM_CHANNEL_MEAN_SQUARE
+        || right_mean_square < MINIMUM_CHANNEL_MEAN_SQUARE
+    {
+        return None;
+    }
+
+    let correlation = cross_mean / (left_mean_square * right_mean_square).sqrt();
+
+    Some(StereoImage {
+        correlation: correlation.clamp(-1.0, 1.0) as f32,
+        balance_db: (10.0 * (right_mean_square / left_mean_square).log10()) as f32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::generate_sine_samples;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn return_full_correlation_and_centre_balance_for_identical_channels() {
+        let samples = generate_sine_samples(1000.0, 0.5, 0.0, 48000.0, 4800);
+        let mut meter = StereoImageMeter::default();
+
+        let image = meter.update(&samples, &samples).unwrap();
+        assert!((image.correlation - 1.0).abs() < 0.001);
+        assert!(image.balance_db.abs() < 0.001);
+    }
+
+    #[test]
+    fn return_negative_correlation_for_inverted_channel() {
+        let left = generate_sine_samples(1000.0, 0.5, 0.0, 48000.0, 4800);
+        let right: Vec<f32> = left.iter().map(|sample| -sample).collect();
+        let mut meter = StereoImageMeter::default();
+
+        let image = meter.update(&left, &right).unwrap();
+        assert!((image.correlation + 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn return_zero_correlation_for_quadrature_channels() {
+        let left = generate_sine_samples(1000.0, 0.5, 0.0, 48000.0, 4800);
+        let right = generate_sine_samples(1000.0, 0.5, PI / 2.0, 48000.0, 4800);
+        let mut meter = StereoImageMeter::default();
+
+        let image = meter.update(&left, &right).unwrap();
+        assert!(image.correlation.abs() < 0.01);
+    }
+
+    #[test]
+    fn return_positive_balance_when_right_channel_is_louder() {
+        let left = generate_sine_samples(1000.0, 0.25, 0.0, 48000.0, 4800);
+        let right = generate_sine_samples(1000.0, 0.5, 0.0, 48000.0, 4800);
+        let mut meter = StereoImageMeter::default();
+
+        let image = meter.update(&left, &right).unwrap();
+        assert!((image.balance_db - 6.02).abs() < 0.01);
+    }
+
+    #[test]
+    fn return_none_when_a_channel_is_silent_or_missing() {
+        let left = generate_sine_samples(1000.0, 0.5, 0.0, 48000.0, 4800);
+        let silent = vec![0.0; left.len()];
+        let mut meter = StereoImageMeter::default();
+
+        assert!(meter.update(&left, &silent).is_none());
+        assert!(meter.update(&left, &[]).is_none());
+    }
+}
@@ -0,0 +1,47 @@
+const A_WEIGHTING_POLE_1: f64 = 20.598997;
+const A_WEIGHTING_POLE_2: f64 = 107.65265;
+const A_WEIGHTING_POLE_3: f64 = 737.86223;
+const A_WEIGHTING_POLE_4: f64 = 12194.217;
+const A_WEIGHTING_GAIN_AT_ONE_KILOHERTZ_IN_DB: f64 = 2.0;
+
+pub fn get_a_weighting_gain(frequency: f32) -> f64 {
+    let frequency_squared = f64::from(frequency) * f64::from(frequency);
+
+    if frequency_squared <= 0.0 {
+        return 0.0;
+    }
+
+    let numerator = A_WEIGHTING_POLE_4.powi(2) * frequency_squared * frequency_squared;
+    let denominator = (frequency_squared + A_WEIGHTING_POLE_1.powi(2))
+        * ((frequency_squared + A_WEIGHTING_POLE_2.powi(2))
+            * (frequency_squared + A_WEIGHTING_POLE_3.powi(2)))
+        .sqrt()
+        * (frequency_squared + A_WEIGHTING_POLE_4.powi(2));
+
+    numerator / denominator * 10.0_f64.powf(A_WEIGHTING_GAIN_AT_ONE_KILOHERTZ_IN_DB / 20.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_gain_in_db(frequency: f32) -> f64 {
+        20.0 * get_a_weighting_gain(frequency).log10()
+    }
+
+    #[test]
+    fn return_unity_a_weighting_gain_at_one_kilohertz() {
+        assert!(get_gain_in_db(1000.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn return_standard_a_weighting_gain_at_reference_frequencies() {
+        assert!((get_gain_in_db(100.0) + 19.1).abs() < 0.1);
+        assert!((get_gain_in_db(10000.0) + 2.5).abs() < 0.1);
+    }
+
+    #[test]
+    fn return_zero_a_weighting_gain_at_zero_hertz() {
+        assert_eq!(get_a_weighting_gain(0.0), 0.0);
+    }
+}
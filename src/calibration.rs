@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const DBU_REFERENCE_VOLTS: f32 = 0.774_596_7;
+const DBV_REFERENCE_VOLTS: f32 = 1.0;
+const MILLIVOLT_THRESHOLD: f32 = 0.01;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CalibrationDirection {
+    Input,
+    Output,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnalogUnit {
+    #[default]
+    Dbfs,
+    Dbu,
+    Dbv,
+    #[serde(rename = "vrms")]
+    VoltsRms,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calibration {
+    pub dbfs: f32,
+    pub dbu: f32,
+}
+
+impl Calibration {
+    pub fn get_dbu_from_dbfs(&self, level: f32) -> f32 {
+        level - self.dbfs + self.dbu
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeviceCalibrations {
+    pub input: BTreeMap<String, Calibration>,
+    pub output: BTreeMap<String, Calibration>,
+}
+
+impl DeviceCalibrations {
+    pub fn get(&self, direction: CalibrationDirection, device_name: &str) -> Option<Calibration> {
+        self.get_map(direction).get(device_name).copied()
+    }
+
+    pub fn set(
+        &mut self,
+        direction: CalibrationDirection,
+        device_name: &str,
+        calibration: Option<Calibration>,
+    ) {
+        let map = match direction {
+            CalibrationDirection::Input => &mut self.input,
+            CalibrationDirection::Output => &mut self.output,
+        };
+
+        match calibration {
+            Some(calibration) => map.insert(device_name.to_string(), calibration),
+            None => map.remove(device_name),
+        };
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.input.is_empty() && self.output.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (CalibrationDirection, &String, &Calibration)> {
+        self.input
+            .iter()
+            .map(|(name, calibration)| (CalibrationDirection::Input, name, calibration))
+            .chain(
+                self.output
+                    .iter()
+                    .map(|(name, calibration)| (CalibrationDirection::Output, name, calibration)),
+            )
+    }
+
+    fn get_map(&self, direction: CalibrationDirection) -> &BTreeMap<String, Calibration> {
+        match direction {
+            CalibrationDirection::Input => &self.input,
+            CalibrationDirection::Output => &self.output,
+        }
+    }
+}
+
+pub fn get_calibration_from_text(dbfs: &str, dbu: &str) -> Option<Calibration> {
+    let dbfs: f32 = dbfs.trim().parse().ok().filter(|dbfs: &f32| dbfs.is_finite())?;
+    let dbu: f32 = dbu.trim().parse().ok().filter(|dbu: &f32| dbu.is_finite())?;
+    Some(Calibration { dbfs, dbu })
+}
+
+pub fn get_volts_from_dbu(dbu: f32) -> f32 {
+    DBU_REFERENCE_VOLTS * 10.0_f32.powf(dbu / 20.0)
+}
+
+pub fn get_dbv_from_dbu(dbu: f32) -> f32 {
+    dbu + 20.0 * (DBU_REFERENCE_VOLTS / DBV_REFERENCE_VOLTS).log10()
+}
+
+pub fn format_analog_level_for_display(
+    level: f32,
+    calibration: Option<Calibration>,
+    unit: AnalogUnit,
+) -> Option<(String, &'static str)> {
+    let calibration = calibration?;
+    if !level.is_finite() {
+        return None;
+    }
+
+    let dbu = calibration.get_dbu_from_dbfs(level);
+    match unit {
+        AnalogUnit::Dbfs => None,
+        AnalogUnit::Dbu => Some((format_decibels_for_display(dbu), "dBu")),
+        AnalogUnit::Dbv => Some((format_decibels_for_display(get_dbv_from_dbu(dbu)), "dBV")),
+        AnalogUnit::VoltsRms => Some(format_volts_for_display(get_volts_from_dbu(dbu))),
+    }
+}
+
+pub fn format_calibration_for_display(calibration: Calibration) -> String {
+    format!(
+        "{} dBFS = {} dBu",
+        format_decibels_for_display(calibration.dbfs),
+        format_decibels_for_display(calibration.dbu)
+    )
+}
+
+fn format_decibels_for_display(value: f32) -> String {
+    let rounded = (value * 10.0).round() / 10.0 + 0.0;
+    if rounded > 0.0 {
+        format!("+{rounded:.1}")
+    } else {
+        format!("{rounded:.1}")
+    }
+}
+
+fn format_volts_for_display(volts: f32) -> (String, &'static str) {
+    if volts < MILLIVOLT_THRESHOLD {
+        (format!("{:.2}", volts * 1000.0), "mV rms")
+    } else if volts < 1.0 {
+        (format!("{volts:.3}"), "V rms")
+    } else if volts < 10.0 {
+        (format!("{volts:.2}"), "V rms")
+    } else {
+        (format!("{volts:.1}"), "V rms")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLUS_FOUR_AT_MINUS_EIGHTEEN: Calibration = Calibration {
+        dbfs: -18.0,
+        dbu: 4.0,
+    };
+
+    #[test]
+    fn map_dbfs_to_dbu_through_the_calibration_point() {
+        assert_eq!(PLUS_FOUR_AT_MINUS_EIGHTEEN.get_dbu_from_dbfs(-18.0), 4.0);
+        assert_eq!(PLUS_FOUR_AT_MINUS_EIGHTEEN.get_dbu_from_dbfs(0.0), 22.0);
+        assert!((get_volts_from_dbu(0.0) - 0.7746).abs() < 0.0001);
+        assert!((get_dbv_from_dbu(0.0) + 2.2185).abs() < 0.0001);
+    }
+
+    #[test]
+    fn format_analog_level_in_the_selected_unit() {
+        let format = |level, unit| {
+            format_analog_level_for_display(level, Some(PLUS_FOUR_AT_MINUS_EIGHTEEN), unit)
+        };
+        assert_eq!(format(-18.0, AnalogUnit::Dbu), Some(("+4.0".to_string(), "dBu")));
+        assert_eq!(format(-22.0, AnalogUnit::Dbu), Some(("0.0".to_string(), "dBu")));
+        assert_eq!(format(-18.0, AnalogUnit::Dbv), Some(("+1.8".to_string(), "dBV")));
+        assert_eq!(format(-18.0, AnalogUnit::VoltsRms), Some(("1.23".to_string(), "V rms")));
+        assert_eq!(format(-30.0, AnalogUnit::VoltsRms), Some(("0.308".to_string(), "V rms")));
+        assert_eq!(format(-70.0, AnalogUnit::VoltsRms), Some(("3.08".to_string(), "mV rms")));
+        assert_eq!(format(-18.0, AnalogUnit::Dbfs), None);
+        assert_eq!(format(f32::NEG_INFINITY, AnalogUnit::Dbu), None);
+        assert_eq!(format_analog_level_for_display(-18.0, None, AnalogUnit::Dbu), None);
+    }
+
+    #[test]
+    fn keep_one_calibration_per_device_and_direction() {
+        let mut calibrations = DeviceCalibrations::default();
+        let full_scale = Calibration {
+            dbfs: 0.0,
+            dbu: 24.0,
+        };
+
+        calibrations.set(CalibrationDirection::Input, "Interface", Some(full_scale));
+        calibrations.set(
+            CalibrationDirection::Output,
+            "Interface",
+            Some(PLUS_FOUR_AT_MINUS_EIGHTEEN),
+        );
+        assert_eq!(
+            calibrations.get(CalibrationDirection::Input, "Interface"),
+            Some(full_scale)
+        );
+        assert_eq!(
+            calibrations.get(CalibrationDirection::Output, "Interface"),
+            Some(PLUS_FOUR_AT_MINUS_EIGHTEEN)
+        );
+
+        calibrations.set(CalibrationDirection::Input, "Interface", None);
+        assert_eq!(calibrations.get(CalibrationDirection::Input, "Interface"), None);
+        assert_eq!(calibrations.iter().count(), 1);
+    }
+
+    #[test]
+    fn return_calibration_from_text_only_when_both_values_are_numbers() {
+        assert_eq!(
+            get_calibration_from_text(" -18 ", "+4"),
+            Some(PLUS_FOUR_AT_MINUS_EIGHTEEN)
+        );
+        assert_eq!(get_calibration_from_text("", "4"), None);
+        assert_eq!(get_calibration_from_text("-18", "inf"), None);
+    }
+}
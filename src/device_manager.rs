@@ -1,8 +1,10 @@
 use crate::errors::LocalError;
 use crate::events::EventType;
+use crate::settings::Settings;
 use cpal::traits::{DeviceTrait, HostTrait};
 use cpal::{Device, default_host};
 use crossbeam_channel::Sender;
+use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::thread::sleep;
 use std::time::Duration;
@@ -15,10 +17,11 @@ pub struct DeviceList {
     pub channels: Vec<Vec<String>>,
 }
 
-#[derive(Clone, Default, Debug, PartialEq)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct CurrentDevice {
     pub name: String,
     pub left_channel: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub right_channel: Option<String>,
 }
 
@@ -37,11 +40,14 @@ impl DeviceManager {
         user_interface_sender: Sender<EventType>,
         input_device_sender: Sender<EventType>,
         output_device_sender: Sender<EventType>,
+        settings: &Settings,
     ) -> Result<Self, Box<dyn Error>> {
         let input_devices = get_input_device_list_from_host()?;
-        let current_input_device = get_default_input_device_data(&input_devices)?;
+        let current_input_device =
+            get_default_input_device_data(&input_devices, settings.input_device.as_ref())?;
         let output_devices = get_output_device_list_from_host()?;
-        let current_output_device = get_default_output_device_data(&output_devices)?;
+        let current_output_device =
+            get_default_output_device_data(&output_devices, settings.output_device.as_ref())?;
 
         Ok(Self {
             user_interface_sender,
@@ -112,10 +118,11 @@ impl DeviceManager {
 
 fn get_default_input_device_data(
     input_devices: &DeviceList,
+    saved_device: Option<&CurrentDevice>,
 ) -> Result<CurrentDevice, Box<dyn Error>> {
     let host = default_host();
 
-    let name = input_devices.devices[0].clone();
+    let name = get_initial_device_name(input_devices, saved_device);
 
     let device = host
         .input_devices()?
@@ -129,27 +136,20 @@ fn get_default_input_device_data(
 
     let default_input_channels = get_channel_list_from_input_device(&device);
 
-    let left_channel = default_input_channels[0].clone();
-
-    let right_channel = if default_input_channels.len() > 1 {
-        Some(default_input_channels[1].clone())
-    } else {
-        None
-    };
-
-    Ok(CurrentDevice {
+    Ok(get_device_with_saved_channels(
         name,
-        left_channel,
-        right_channel,
-    })
+        &default_input_channels,
+        saved_device,
+    ))
 }
 
 fn get_default_output_device_data(
     output_devices: &DeviceList,
+    saved_device: Option<&CurrentDevice>,
 ) -> Result<CurrentDevice, Box<dyn Error>> {
     let host = default_host();
 
-    let name = output_devices.devices[0].clone();
+    let name = get_initial_device_name(output_devices, saved_device);
 
     let device = host
         .output_devices()?
@@ -163,19 +163,49 @@ fn get_default_output_device_data(
 
     let default_output_channels = get_channel_list_from_output_device(&device);
 
-    let left_channel = default_output_channels[0].clone();
+    Ok(get_device_with_saved_channels(
+        name,
+        &default_output_channels,
+        saved_device,
+    ))
+}
 
-    let right_channel = if default_output_channels.len() > 1 {
-        Some(default_output_channels[1].clone())
+fn get_initial_device_name(device_list: &DeviceList, saved_device: Option<&CurrentDevice>) -> String {
+    saved_device
+        .filter(|saved_device| device_list.devices.contains(&saved_device.name))
+        .map(|saved_device| saved_device.name.clone())
+        .unwrap_or_else(|| device_list.devices[0].clone())
+}
+
+pub fn get_device_with_saved_channels(
+    name: String,
+    channels: &[String],
+    saved_device: Option<&CurrentDevice>,
+) -> CurrentDevice {
+    if let Some(saved_device) = saved_device.filter(|saved_device| {
+        saved_device.name == name
+            && channels.contains(&saved_device.left_channel)
+            && saved_device
+                .right_channel
+                .as_ref()
+                .is_none_or(|right_channel| channels.contains(right_channel))
+    }) {
+        return saved_device.clone();
+    }
+
+    let left_channel = channels[0].clone();
+
+    let right_channel = if channels.len() > 1 {
+        Some(channels[1].clone())
     } else {
         None
     };
 
-    Ok(CurrentDevice {
+    CurrentDevice {
         name,
         left_channel,
         right_channel,
-    })
+    }
 }
 
 fn get_input_device_list_from_host() -> Result<DeviceList, Box<dyn Error>> {
@@ -312,4 +342,50 @@ mod tests {
         assert_eq!(left, 1);
         assert_eq!(right, None);
     }
+
+    fn get_test_device_list() -> DeviceList {
+        DeviceList {
+            devices: vec!["Built-in".to_string(), "Interface".to_string()],
+            channels: vec![
+                vec!["1".to_string(), "2".to_string()],
+                vec!["1".to_string(), "2".to_string(), "3".to_string(), "4".to_string()],
+            ],
+        }
+    }
+
+    fn get_saved_device(name: &str, left: &str, right: Option<&str>) -> CurrentDevice {
+        CurrentDevice {
+            name: name.to_string(),
+            left_channel: left.to_string(),
+            right_channel: right.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn prefer_the_saved_device_when_it_is_present() {
+        let saved_device = get_saved_device("Interface", "3", Some("4"));
+        let name = get_initial_device_name(&get_test_device_list(), Some(&saved_device));
+        assert_eq!(name, "Interface");
+    }
+
+    #[test]
+    fn fall_back_to_the_first_device_when_the_saved_device_is_missing() {
+        let saved_device = get_saved_device("Unplugged", "1", None);
+        let device_list = get_test_device_list();
+        assert_eq!(get_initial_device_name(&device_list, Some(&saved_device)), "Built-in");
+        assert_eq!(get_initial_device_name(&device_list, None), "Built-in");
+    }
+
+    #[test]
+    fn restore_saved_channels_only_when_they_exist_on_the_device() {
+        let channels = &get_test_device_list().channels[1];
+
+        let saved_device = get_saved_device("Interface", "3", Some("4"));
+        let device = get_device_with_saved_channels("Interface".to_string(), channels, Some(&saved_device));
+        assert_eq!(device, saved_device);
+
+        let saved_device = get_saved_device("Interface", "3", Some("8"));
+        let device = get_device_with_saved_channels("Interface".to_string(), channels, Some(&saved_device));
+        assert_eq!(device, get_saved_device("Interface", "1", Some("2")));
+    }
 }
@@ -13,6 +13,9 @@ pub enum LocalError {
     #[error("Error sending level meter value updates to the UI")]
     LevelMeterUISender,
 
+    #[error("Error sending captured samples to the measurement manager")]
+    LevelMeterMeasurementSender,
+
     #[error("Error retrieving shared Level Meter receiver. Recovering Data.")]
     LevelMeterReceiver,
 
@@ -52,6 +55,9 @@ pub enum LocalError {
     #[error("Failed to configure an output stream: {0}")]
     ToneGeneratorOutputStream(String),
 
+    #[error("Failed to open the guide output: {0}")]
+    GuideOutputStream(String),
+
     #[error("Error Initializing User Interface")]
     UIInitialization,
 
@@ -82,6 +88,42 @@ pub enum LocalError {
     #[error("Failed to generate channel index: {0}")]
     ChannelIndex(String),
 
+    #[error("Error Initializing Measurement Manager")]
+    MeasurementInitialization,
+
+    #[error("Error sending measurement commands: {0}")]
+    MeasurementSender(String),
+
+    #[error("Invalid measurement configuration: {0}")]
+    MeasurementConfiguration(String),
+
+    #[error("No input signal was detected while measuring at {0} Hz")]
+    MeasurementNoSignal(String),
+
+    #[error("Failed to export the measurement: {0}")]
+    MeasurementExport(String),
+
+    #[error("Failed to read the saved settings: {0}")]
+    SettingsRead(String),
+
+    #[error("Failed to save the settings: {0}")]
+    SettingsWrite(String),
+
+    #[error("Failed to read the preset {0}")]
+    PresetRead(String),
+
+    #[error("Failed to save the preset: {0}")]
+    PresetWrite(String),
+
+    #[error("{0} can not be used as a preset name")]
+    PresetName(String),
+
+    #[error("Could not find a user config folder to keep presets in")]
+    PresetDirectory,
+
+    #[error("Could not keep the display awake: {0}")]
+    KeepAwake(String),
+
     #[error("A fatal error has occured and the application is not exiting.")]
     FatalError,
 }
@@ -1,4 +1,21 @@
+use crate::analysis::CapturedSamples;
+use crate::analysis::distortion::{DistortionMeasurement, DistortionSettings};
+use crate::analysis::hum::HumMeasurement;
+use crate::analysis::spectrum::{Spectrum, SpectrumSettings};
+use crate::analysis::stereo_image::StereoImage;
+use crate::calibration::{Calibration, CalibrationDirection};
 use crate::device_manager::DeviceList;
+use crate::level_meter::clip_detector::{ClipCount, ClipSettings};
+use crate::measurement::MeasurementRequest;
+use crate::measurement::crosstalk::CrosstalkResult;
+use crate::measurement::frequency_response::FrequencyResponse;
+use crate::measurement::gain_drift::GainDriftPoint;
+use crate::measurement::latency::LatencyResult;
+use crate::measurement::noise_floor::NoiseFloorResult;
+use crate::measurement::polarity::PolarityResult;
+use crate::measurement::unity_check::UnityCheckResult;
+use crate::tone_generator::{OutputChannels, Waveform};
+use crate::tone_generator::sweep::SweepSettings;
 use crossbeam_channel::{Receiver, Sender, unbounded};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -13,6 +30,35 @@ pub enum EventType {
         left: String,
         right: Option<String>,
     },
+    MeterDistortionSettingsUpdate(DistortionSettings),
+    MeterDistortionUpdate {
+        left: Option<DistortionMeasurement>,
+        right: Option<DistortionMeasurement>,
+    },
+    MeterStereoImageUpdate(Option<StereoImage>),
+    MeterSpectrumSettingsUpdate(SpectrumSettings),
+    MeterReferenceFrequencyUpdate(f32),
+    MeterClipSettingsUpdate(ClipSettings),
+    MeterClipReset,
+    MeterDcBlockingUpdate(bool),
+    MeterMeasurementCaptureUpdate(bool),
+    MeterDcOffsetUpdate {
+        left: Option<f32>,
+        right: Option<f32>,
+    },
+    MeterClipUpdate {
+        left: ClipCount,
+        right: ClipCount,
+    },
+    MeterHumUpdate {
+        left: Option<HumMeasurement>,
+        right: Option<HumMeasurement>,
+    },
+    MeterSpectrumUpdate(Spectrum),
+    MeterFrequencyUpdate {
+        left: Option<f32>,
+        right: Option<f32>,
+    },
     ToneFrequencyUpdate(f32),
     ToneLevelUpdate(f32),
     ToneDeviceUpdate {
@@ -20,7 +66,15 @@ pub enum EventType {
         left: String,
         right: Option<String>,
     },
-    ToneModeUpdate(bool),
+    ToneWaveformUpdate(Waveform),
+    ToneOutputChannelsUpdate(OutputChannels),
+    ToneBurstTrigger,
+    ToneSweepUpdate(SweepSettings),
+    GuideLevelUpdate {
+        delta: Option<f32>,
+        unity_tolerance: f32,
+    },
+    GuideDeviceUpdate(Option<String>),
     InputDeviceListUpdate(DeviceList),
     OutputDeviceListUpdate(DeviceList),
     InputDeviceUpdate(String),
@@ -33,6 +87,32 @@ pub enum EventType {
         left: String,
         right: Option<String>,
     },
+    MeasurementStart(MeasurementRequest),
+    MeasurementStop,
+    MeasurementSamples(CapturedSamples),
+    MeasurementRunningUpdate(bool),
+    MeasurementProgressUpdate(String),
+    FrequencyResponseUpdate(FrequencyResponse),
+    FrequencyResponseExport(String),
+    UnityCheckUpdate(UnityCheckResult),
+    NoiseFloorUpdate(NoiseFloorResult),
+    CrosstalkUpdate(CrosstalkResult),
+    LatencyUpdate(LatencyResult),
+    PolarityUpdate(PolarityResult),
+    GainDriftStart,
+    GainDriftUpdate(GainDriftPoint),
+    GainDriftExport(String),
+    PresetLoad(String),
+    PresetSave(String),
+    PresetImport(String),
+    PresetExport {
+        name: String,
+        directory: String,
+    },
+    CalibrationUpdate {
+        direction: CalibrationDirection,
+        calibration: Option<Calibration>,
+    },
     RecoverableError(String),
     FatalError(String),
     Start,
@@ -47,6 +127,8 @@ pub struct Events {
     level_meter_receiver: Receiver<EventType>,
     user_interface_sender: Sender<EventType>,
     user_interface_receiver: Receiver<EventType>,
+    measurement_sender: Sender<EventType>,
+    measurement_receiver: Receiver<EventType>,
 }
 
 impl Events {
@@ -54,6 +136,7 @@ impl Events {
         let (tone_generator_sender, tone_generator_receiver) = unbounded();
         let (level_meter_sender, level_meter_receiver) = unbounded();
         let (user_interface_sender, user_interface_receiver) = unbounded();
+        let (measurement_sender, measurement_receiver) = unbounded();
 
         Events {
             tone_generator_sender,
@@ -62,6 +145,8 @@ impl Events {
             level_meter_receiver,
             user_interface_sender,
             user_interface_receiver,
+            measurement_sender,
+            measurement_receiver,
         }
     }
 
@@ -88,4 +173,12 @@ impl Events {
     pub fn get_user_interface_receiver(&self) -> Receiver<EventType> {
         self.user_interface_receiver.clone()
     }
+
+    pub fn get_measurement_sender(&self) -> Sender<EventType> {
+        self.measurement_sender.clone()
+    }
+
+    pub fn get_measurement_receiver(&self) -> Receiver<EventType> {
+        self.measurement_receiver.clone()
+    }
 }
@@ -1,14 +1,26 @@
+use crate::analysis::CapturedSamples;
+use crate::analysis::dc_offset::{DcBlockingFilter, get_dc_offset};
+use crate::analysis::distortion::{DistortionSettings, get_distortion_measurement};
+use crate::analysis::fft::{PowerSpectrum, get_block_power_spectrum};
+use crate::analysis::frequency::get_dominant_frequency;
+use crate::analysis::hum::get_hum_measurement;
+use crate::analysis::spectrum::{SpectrumAnalyzer, SpectrumSettings};
+use crate::analysis::stereo_image::StereoImageMeter;
 use crate::device_manager::get_channel_indexes_from_channel_names;
 use crate::errors::{EXIT_CODE_ERROR, LocalError, handle_local_error};
 use crate::events::EventType;
 use cpal::traits::{StreamTrait, DeviceTrait, HostTrait};
-use cpal::{Device, Stream, default_host};
+use cpal::{Device, InputStreamTimestamp, Stream, default_host};
+use clip_detector::{ClipCount, ClipDetector, ClipSettings};
 use crossbeam_channel::{Receiver, Sender};
 use rtrb::{Consumer, Producer, RingBuffer};
 use std::error::Error;
 use std::process::exit;
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Instant;
+
+pub mod clip_detector;
 
 const ERROR_MESSAGE_INPUT_STREAM_ERROR: &str = "Input Stream error!";
 const INPUT_BUFFERS_FOR_PEAK_CALCULATION: usize = 20;
@@ -17,6 +29,8 @@ const RING_BUFFER_SIZE: usize = 1024;
 struct SampleFrameBuffer {
     is_alive: bool,
     error_message: String,
+    sample_rate: f32,
+    captured_at: Instant,
     left: Vec<f32>,
     right: Vec<f32>,
 }
@@ -25,6 +39,12 @@ pub struct LevelMeter {
     input_stream: Option<Stream>,
     sample_consumer: Arc<Mutex<Consumer<SampleFrameBuffer>>>,
     sample_producer: Arc<Mutex<Producer<SampleFrameBuffer>>>,
+    distortion_settings: Arc<Mutex<DistortionSettings>>,
+    spectrum_settings: Arc<Mutex<SpectrumSettings>>,
+    reference_frequency: Arc<Mutex<f32>>,
+    clip_detector: Arc<Mutex<ClipDetector>>,
+    dc_blocking_enabled: Arc<Mutex<bool>>,
+    measurement_capture_active: Arc<Mutex<bool>>,
     ui_command_receiver: Receiver<EventType>,
 }
 
@@ -37,6 +57,12 @@ impl LevelMeter {
             input_stream: None,
             sample_consumer: Arc::new(Mutex::new(sample_consumer)),
             sample_producer: sample_producer_arc,
+            distortion_settings: Arc::new(Mutex::new(DistortionSettings::default())),
+            spectrum_settings: Arc::new(Mutex::new(SpectrumSettings::default())),
+            reference_frequency: Arc::new(Mutex::new(0.0)),
+            clip_detector: Arc::new(Mutex::new(ClipDetector::default())),
+            dc_blocking_enabled: Arc::new(Mutex::new(false)),
+            measurement_capture_active: Arc::new(Mutex::new(false)),
             ui_command_receiver,
         })
     }
@@ -52,6 +78,25 @@ impl LevelMeter {
                     EventType::MeterDeviceUpdate { name, left, right } => {
                         self.update_input_stream_on_new_device(&name, &left, right.as_ref())?;
                     }
+                    EventType::MeterDistortionSettingsUpdate(settings) => {
+                        self.update_distortion_settings(settings);
+                    }
+                    EventType::MeterSpectrumSettingsUpdate(settings) => {
+                        self.update_spectrum_settings(settings);
+                    }
+                    EventType::MeterReferenceFrequencyUpdate(frequency) => {
+                        self.update_reference_frequency(frequency);
+                    }
+                    EventType::MeterClipSettingsUpdate(settings) => {
+                        self.update_clip_settings(settings);
+                    }
+                    EventType::MeterClipReset => self.reset_clip_counts(),
+                    EventType::MeterDcBlockingUpdate(enabled) => {
+                        self.update_dc_blocking_enabled(enabled);
+                    }
+                    EventType::MeterMeasurementCaptureUpdate(active) => {
+                        self.update_measurement_capture_active(active);
+                    }
                     _ => (),
                 }
             }
@@ -76,6 +121,60 @@ impl LevelMeter {
         Ok(())
     }
 
+    fn update_distortion_settings(&mut self, settings: DistortionSettings) {
+        let mut distortion_settings = self
+            .distortion_settings
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *distortion_settings = settings;
+    }
+
+    fn update_spectrum_settings(&mut self, settings: SpectrumSettings) {
+        let mut spectrum_settings = self
+            .spectrum_settings
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *spectrum_settings = settings;
+    }
+
+    fn update_reference_frequency(&mut self, frequency: f32) {
+        let mut reference_frequency = self
+            .reference_frequency
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *reference_frequency = frequency;
+    }
+
+    fn update_clip_settings(&mut self, settings: ClipSettings) {
+        self.clip_detector
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .update_settings(settings);
+    }
+
+    fn reset_clip_counts(&mut self) {
+        self.clip_detector
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .reset();
+    }
+
+    fn update_dc_blocking_enabled(&mut self, enabled: bool) {
+        let mut dc_blocking_enabled = self
+            .dc_blocking_enabled
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *dc_blocking_enabled = enabled;
+    }
+
+    fn update_measurement_capture_active(&mut self, active: bool) {
+        let mut measurement_capture_active = self
+            .measurement_capture_active
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *measurement_capture_active = active;
+    }
+
     fn update_input_stream_on_new_device(
         &mut self,
         device_name: &str,
@@ -109,13 +208,26 @@ impl LevelMeter {
     pub fn run_input_sample_processor(
         &mut self,
         user_interface_sender: Sender<EventType>,
+        measurement_sender: Sender<EventType>,
     ) -> Result<(), Box<dyn Error>> {
         let mut left_input_buffer_collector: Vec<Vec<f32>> = Vec::new();
         let mut right_input_buffer_collector: Vec<Vec<f32>> = Vec::new();
         let mut previous_left_peak: f32 = 0.0;
         let mut previous_right_peak: f32 = 0.0;
+        let mut collected_buffers_captured_at: Option<Instant> = None;
+        let mut stereo_image_meter = StereoImageMeter::default();
+        let mut spectrum_analyzer = SpectrumAnalyzer::default();
 
         let sample_receiver_arc = self.sample_consumer.clone();
+        let distortion_settings_arc = self.distortion_settings.clone();
+        let spectrum_settings_arc = self.spectrum_settings.clone();
+        let reference_frequency_arc = self.reference_frequency.clone();
+        let clip_detector_arc = self.clip_detector.clone();
+        let dc_blocking_enabled_arc = self.dc_blocking_enabled.clone();
+        let measurement_capture_active_arc = self.measurement_capture_active.clone();
+        let mut left_dc_blocking_filter = DcBlockingFilter::default();
+        let mut right_dc_blocking_filter = DcBlockingFilter::default();
+        let mut previous_clip_counts = None;
 
         thread::spawn(move || {
             let mut sample_receiver = sample_receiver_arc
@@ -132,19 +244,38 @@ impl LevelMeter {
                     );
 
                     if left_input_buffer_collector.len() > INPUT_BUFFERS_FOR_PEAK_CALCULATION {
-                        let mut left_samples_buffer: Vec<f32> =
+                        let left_samples_buffer: Vec<f32> =
                             consolidate_sample_buffer_collector_to_sample_buffer(
                                 &mut left_input_buffer_collector,
                             );
-                        let mut right_samples_buffer: Vec<f32> =
+                        let right_samples_buffer: Vec<f32> =
                             consolidate_sample_buffer_collector_to_sample_buffer(
                                 &mut right_input_buffer_collector,
                             );
 
-                        let new_left_peak =
-                            get_peak_value_of_collected_samples(&mut left_samples_buffer);
-                        let new_right_peak =
-                            get_peak_value_of_collected_samples(&mut right_samples_buffer);
+                        let dc_blocking_enabled = *dc_blocking_enabled_arc
+                            .lock()
+                            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+                        let left_blocked_samples = get_dc_blocked_samples(
+                            &left_samples_buffer,
+                            &mut left_dc_blocking_filter,
+                            sample_buffers.sample_rate,
+                            dc_blocking_enabled,
+                        );
+                        let right_blocked_samples = get_dc_blocked_samples(
+                            &right_samples_buffer,
+                            &mut right_dc_blocking_filter,
+                            sample_buffers.sample_rate,
+                            dc_blocking_enabled,
+                        );
+
+                        let new_left_peak = get_peak_value_of_collected_samples(
+                            left_blocked_samples.as_deref().unwrap_or(&left_samples_buffer),
+                        );
+                        let new_right_peak = get_peak_value_of_collected_samples(
+                            right_blocked_samples.as_deref().unwrap_or(&right_samples_buffer),
+                        );
 
                         if previous_left_peak != new_left_peak
                             || previous_right_peak != new_right_peak
@@ -158,10 +289,98 @@ impl LevelMeter {
                                 new_right_peak,
                             );
                         }
+
+                        send_dc_offsets_to_the_ui(
+                            &user_interface_sender,
+                            &left_samples_buffer,
+                            &right_samples_buffer,
+                        );
+
+                        send_clip_counts_to_the_ui_when_changed(
+                            &user_interface_sender,
+                            &clip_detector_arc,
+                            &mut previous_clip_counts,
+                            &left_samples_buffer,
+                            &right_samples_buffer,
+                        );
+
+                        let left_spectrum =
+                            get_block_power_spectrum(&left_samples_buffer, sample_buffers.sample_rate);
+                        let right_spectrum =
+                            get_block_power_spectrum(&right_samples_buffer, sample_buffers.sample_rate);
+
+                        let distortion_settings = *distortion_settings_arc
+                            .lock()
+                            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+                        send_distortion_measurements_to_the_ui(
+                            &user_interface_sender,
+                            left_spectrum.as_ref(),
+                            right_spectrum.as_ref(),
+                            &distortion_settings,
+                        );
+
+                        send_input_frequencies_to_the_ui(
+                            &user_interface_sender,
+                            left_spectrum.as_ref(),
+                            right_spectrum.as_ref(),
+                        );
+
+                        let reference_frequency = *reference_frequency_arc
+                            .lock()
+                            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+                        send_hum_measurements_to_the_ui(
+                            &user_interface_sender,
+                            left_spectrum.as_ref(),
+                            right_spectrum.as_ref(),
+                            reference_frequency,
+                        );
+
+                        let spectrum_settings = *spectrum_settings_arc
+                            .lock()
+                            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+                        send_spectrum_to_the_ui(
+                            &user_interface_sender,
+                            &mut spectrum_analyzer,
+                            &left_samples_buffer,
+                            &right_samples_buffer,
+                            (left_spectrum.as_ref(), right_spectrum.as_ref()),
+                            sample_buffers.sample_rate,
+                            &spectrum_settings,
+                        );
+
+                        send_stereo_image_to_the_ui(
+                            &user_interface_sender,
+                            &mut stereo_image_meter,
+                            &left_samples_buffer,
+                            &right_samples_buffer,
+                        );
+
+                        let measurement_capture_active = *measurement_capture_active_arc
+                            .lock()
+                            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+                        if measurement_capture_active {
+                            send_captured_samples_to_the_measurement_manager(
+                                &measurement_sender,
+                                CapturedSamples {
+                                    left: left_blocked_samples.unwrap_or(left_samples_buffer),
+                                    right: right_blocked_samples.unwrap_or(right_samples_buffer),
+                                    sample_rate: sample_buffers.sample_rate,
+                                    captured_at: collected_buffers_captured_at.take(),
+                                },
+                            );
+                        }
+                    }
+
+                    if left_input_buffer_collector.is_empty() {
+                        collected_buffers_captured_at = Some(sample_buffers.captured_at);
                     }
 
-                    left_input_buffer_collector.insert(0, sample_buffers.left);
-                    right_input_buffer_collector.insert(0, sample_buffers.right);
+                    left_input_buffer_collector.push(sample_buffers.left);
+                    right_input_buffer_collector.push(sample_buffers.right);
                 }
             }
         });
@@ -196,6 +415,138 @@ fn send_updated_meter_values_to_the_ui(
     }
 }
 
+fn send_dc_offsets_to_the_ui(
+    user_interface_sender: &Sender<EventType>,
+    left_samples: &[f32],
+    right_samples: &[f32],
+) {
+    let left = get_dc_offset(left_samples);
+    let right = get_dc_offset(right_samples);
+
+    if let Err(error) = user_interface_sender.send(EventType::MeterDcOffsetUpdate { left, right }) {
+        handle_local_error(&LocalError::LevelMeterUISender, &error.to_string());
+        exit(EXIT_CODE_ERROR);
+    }
+}
+
+fn send_clip_counts_to_the_ui_when_changed(
+    user_interface_sender: &Sender<EventType>,
+    clip_detector: &Arc<Mutex<ClipDetector>>,
+    previous_clip_counts: &mut Option<(ClipCount, ClipCount)>,
+    left_samples: &[f32],
+    right_samples: &[f32],
+) {
+    let clip_counts = {
+        let mut clip_detector = clip_detector
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        clip_detector.process(left_samples, right_samples);
+        (clip_detector.get_left_count(), clip_detector.get_right_count())
+    };
+
+    if *previous_clip_counts == Some(clip_counts) {
+        return;
+    }
+    *previous_clip_counts = Some(clip_counts);
+
+    let (left, right) = clip_counts;
+    if let Err(error) = user_interface_sender.send(EventType::MeterClipUpdate { left, right }) {
+        handle_local_error(&LocalError::LevelMeterUISender, &error.to_string());
+        exit(EXIT_CODE_ERROR);
+    }
+}
+
+fn send_distortion_measurements_to_the_ui(
+    user_interface_sender: &Sender<EventType>,
+    left_spectrum: Option<&PowerSpectrum>,
+    right_spectrum: Option<&PowerSpectrum>,
+    settings: &DistortionSettings,
+) {
+    let left = left_spectrum.and_then(|spectrum| get_distortion_measurement(spectrum, settings));
+    let right = right_spectrum.and_then(|spectrum| get_distortion_measurement(spectrum, settings));
+
+    if let Err(error) =
+        user_interface_sender.send(EventType::MeterDistortionUpdate { left, right })
+    {
+        handle_local_error(&LocalError::LevelMeterUISender, &error.to_string());
+        exit(EXIT_CODE_ERROR);
+    }
+}
+
+fn send_input_frequencies_to_the_ui(
+    user_interface_sender: &Sender<EventType>,
+    left_spectrum: Option<&PowerSpectrum>,
+    right_spectrum: Option<&PowerSpectrum>,
+) {
+    let left = left_spectrum.and_then(get_dominant_frequency);
+    let right = right_spectrum.and_then(get_dominant_frequency);
+
+    if let Err(error) = user_interface_sender.send(EventType::MeterFrequencyUpdate { left, right }) {
+        handle_local_error(&LocalError::LevelMeterUISender, &error.to_string());
+        exit(EXIT_CODE_ERROR);
+    }
+}
+
+fn send_hum_measurements_to_the_ui(
+    user_interface_sender: &Sender<EventType>,
+    left_spectrum: Option<&PowerSpectrum>,
+    right_spectrum: Option<&PowerSpectrum>,
+    reference_frequency: f32,
+) {
+    let left = left_spectrum.and_then(|spectrum| get_hum_measurement(spectrum, reference_frequency));
+    let right = right_spectrum.and_then(|spectrum| get_hum_measurement(spectrum, reference_frequency));
+
+    if let Err(error) = user_interface_sender.send(EventType::MeterHumUpdate { left, right }) {
+        handle_local_error(&LocalError::LevelMeterUISender, &error.to_string());
+        exit(EXIT_CODE_ERROR);
+    }
+}
+
+fn send_spectrum_to_the_ui(
+    user_interface_sender: &Sender<EventType>,
+    spectrum_analyzer: &mut SpectrumAnalyzer,
+    left_samples: &[f32],
+    right_samples: &[f32],
+    block_spectra: (Option<&PowerSpectrum>, Option<&PowerSpectrum>),
+    sample_rate: f32,
+    settings: &SpectrumSettings,
+) {
+    let Some(spectrum) =
+        spectrum_analyzer.update(left_samples, right_samples, block_spectra, sample_rate, settings)
+    else {
+        return;
+    };
+
+    if let Err(error) = user_interface_sender.send(EventType::MeterSpectrumUpdate(spectrum)) {
+        handle_local_error(&LocalError::LevelMeterUISender, &error.to_string());
+        exit(EXIT_CODE_ERROR);
+    }
+}
+
+fn send_stereo_image_to_the_ui(
+    user_interface_sender: &Sender<EventType>,
+    stereo_image_meter: &mut StereoImageMeter,
+    left_samples: &[f32],
+    right_samples: &[f32],
+) {
+    let stereo_image = stereo_image_meter.update(left_samples, right_samples);
+
+    if let Err(error) = user_interface_sender.send(EventType::MeterStereoImageUpdate(stereo_image)) {
+        handle_local_error(&LocalError::LevelMeterUISender, &error.to_string());
+        exit(EXIT_CODE_ERROR);
+    }
+}
+
+fn send_captured_samples_to_the_measurement_manager(
+    measurement_sender: &Sender<EventType>,
+    captured_samples: CapturedSamples,
+) {
+    if let Err(error) = measurement_sender.send(EventType::MeasurementSamples(captured_samples)) {
+        handle_local_error(&LocalError::LevelMeterMeasurementSender, &error.to_string());
+        exit(EXIT_CODE_ERROR);
+    }
+}
+
 fn consolidate_sample_buffer_collector_to_sample_buffer(
     input_buffer_collector: &mut Vec<Vec<f32>>,
 ) -> Vec<f32> {
@@ -215,6 +566,7 @@ fn create_input_stream(
         .map_err(|err| LocalError::DeviceConfiguration(err.to_string()))?;
     let stream_config = default_device_configuration.config();
     let number_of_channels = stream_config.channels;
+    let sample_rate = stream_config.sample_rate as f32;
     let mut left_channel_samples = Vec::new();
     let mut right_channel_samples = Vec::new();
 
@@ -223,7 +575,9 @@ fn create_input_stream(
     device
         .build_input_stream(
             &stream_config,
-            move |data: &[f32], _: &cpal::InputCallbackInfo| {
+            move |data: &[f32], info: &cpal::InputCallbackInfo| {
+                let captured_at = get_capture_instant(Instant::now(), &info.timestamp());
+
                 data.chunks_exact(number_of_channels as usize)
                     .for_each(|frame| {
                         left_channel_samples.push(frame[left_channel_index]);
@@ -241,6 +595,8 @@ fn create_input_stream(
                 if let Err(err) = sample_producer.push(SampleFrameBuffer {
                     is_alive: true,
                     error_message: String::new(),
+                    sample_rate,
+                    captured_at,
                     left: left_channel_samples.clone(),
                     right: right_channel_samples.clone(),
                 }) {
@@ -260,6 +616,8 @@ fn create_input_stream(
                 if let Err(err) = error_producer.push(SampleFrameBuffer {
                     is_alive: false,
                     error_message: error.to_string(),
+                    sample_rate,
+                    captured_at: Instant::now(),
                     left: Vec::new(),
                     right: Vec::new(),
                 }) {
@@ -272,6 +630,15 @@ fn create_input_stream(
         .map_err(|err| LocalError::LevelMeterConfigureInputStream(err.to_string()))
 }
 
+fn get_capture_instant(callback_instant: Instant, timestamp: &InputStreamTimestamp) -> Instant {
+    let buffer_delay = timestamp
+        .callback
+        .duration_since(&timestamp.capture)
+        .unwrap_or_default();
+
+    callback_instant.checked_sub(buffer_delay).unwrap_or(callback_instant)
+}
+
 fn get_input_device_from_device_name(device_name: &str) -> Result<Device, LocalError> {
     let host = default_host();
 
@@ -289,7 +656,21 @@ fn get_input_device_from_device_name(device_name: &str) -> Result<Device, LocalE
     }
 }
 
-fn get_peak_value_of_collected_samples(samples: &mut [f32]) -> f32 {
+pub(crate) fn get_dc_blocked_samples(
+    samples: &[f32],
+    dc_blocking_filter: &mut DcBlockingFilter,
+    sample_rate: f32,
+    dc_blocking_enabled: bool,
+) -> Option<Vec<f32>> {
+    if !dc_blocking_enabled {
+        dc_blocking_filter.reset();
+        return None;
+    }
+
+    Some(dc_blocking_filter.process(samples, sample_rate))
+}
+
+pub fn get_peak_value_of_collected_samples(samples: &[f32]) -> f32 {
     let peak = samples.iter().fold(0.0f32, |acc, &x| x.abs().max(acc));
     get_dbfs_from_sample_value(peak)
 }
@@ -301,20 +682,52 @@ fn get_dbfs_from_sample_value(sample: f32) -> f32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analysis::generate_sine_samples;
+    use std::time::Duration;
 
     #[test]
     fn return_correct_peak_of_sine_wave_samples() {
-        let mut test_samples = [0.1, -0.5, 0.3, 0.7, -0.2];
-        let peak_sample = get_peak_value_of_collected_samples(&mut test_samples);
+        let test_samples = [0.1, -0.5, 0.3, 0.7, -0.2];
+        let peak_sample = get_peak_value_of_collected_samples(&test_samples);
         // The peak is 0.7, so dbfs should be 20*log10(0.7)
         let expected_result = 20.0 * 0.7_f32.abs().log10();
         assert!((peak_sample - expected_result).abs() < 1e-5);
     }
 
+    #[test]
+    fn dc_blocking_removes_offset_from_peak_only_when_enabled() {
+        let samples: Vec<f32> = generate_sine_samples(100.0, 0.5, 0.0, 48000.0, 96000)
+            .iter()
+            .map(|sample| 0.1 + sample)
+            .collect();
+        let mut filter = DcBlockingFilter::default();
+
+        assert_eq!(get_dc_blocked_samples(&samples, &mut filter, 48000.0, false), None);
+        let unfiltered_peak = get_peak_value_of_collected_samples(&samples);
+        assert!((unfiltered_peak - 20.0 * 0.6_f32.log10()).abs() < 0.01);
+
+        get_dc_blocked_samples(&samples, &mut filter, 48000.0, true);
+        let filtered_samples = get_dc_blocked_samples(&samples, &mut filter, 48000.0, true).unwrap();
+        let filtered_peak = get_peak_value_of_collected_samples(&filtered_samples);
+        assert!((filtered_peak - 20.0 * 0.5_f32.log10()).abs() < 0.01);
+    }
+
+    #[test]
+    fn capture_instant_marks_the_first_sample_not_the_buffer_delivery() {
+        let delivered_at = Instant::now();
+        let timestamp = InputStreamTimestamp {
+            callback: cpal::StreamInstant::new(5, 10_000_000),
+            capture: cpal::StreamInstant::new(5, 0),
+        };
+
+        let captured_at = get_capture_instant(delivered_at, &timestamp);
+        assert_eq!(delivered_at - captured_at, Duration::from_millis(10));
+    }
+
     #[test]
     fn return_neg_infinity_for_peak_of_sine_wave_samples_when_samples_are_empty() {
-        let mut test_samples: [f32; 0] = [];
-        let dbfs = get_peak_value_of_collected_samples(&mut test_samples);
+        let test_samples: [f32; 0] = [];
+        let dbfs = get_peak_value_of_collected_samples(&test_samples);
         assert_eq!(dbfs, f32::NEG_INFINITY);
     }
 
@@ -0,0 +1,152 @@
+const FULL_SCALE_SAMPLE_VALUE: f32 = 1.0;
+const DEFAULT_CLIP_THRESHOLD_DBFS: f32 = -0.1;
+const DEFAULT_CONSECUTIVE_SAMPLES_FOR_AN_OVER: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClipSettings {
+    pub threshold_dbfs: f32,
+    pub consecutive_samples: usize,
+}
+
+impl Default for ClipSettings {
+    fn default() -> Self {
+        Self {
+            threshold_dbfs: DEFAULT_CLIP_THRESHOLD_DBFS,
+            consecutive_samples: DEFAULT_CONSECUTIVE_SAMPLES_FOR_AN_OVER,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ClipCount {
+    pub overs: u64,
+    pub full_scale_samples: u64,
+}
+
+impl ClipCount {
+    pub fn is_clipped(&self) -> bool {
+        self.overs > 0 || self.full_scale_samples > 0
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ChannelClipDetector {
+    count: ClipCount,
+    samples_above_threshold: usize,
+}
+
+impl ChannelClipDetector {
+    fn process(&mut self, samples: &[f32], threshold: f32, consecutive_samples: usize) {
+        for sample in samples {
+            let magnitude = sample.abs();
+
+            if magnitude >= FULL_SCALE_SAMPLE_VALUE {
+                self.count.full_scale_samples += 1;
+            }
+
+            if magnitude >= threshold {
+                self.samples_above_threshold += 1;
+                if self.samples_above_threshold == consecutive_samples {
+                    self.count.overs += 1;
+                }
+            } else {
+                self.samples_above_threshold = 0;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ClipDetector {
+    settings: ClipSettings,
+    left: ChannelClipDetector,
+    right: ChannelClipDetector,
+}
+
+impl ClipDetector {
+    pub fn process(&mut self, left_samples: &[f32], right_samples: &[f32]) {
+        let threshold = 10.0_f32.powf(self.settings.threshold_dbfs / 20.0);
+        let consecutive_samples = self.settings.consecutive_samples.max(1);
+
+        self.left.process(left_samples, threshold, consecutive_samples);
+        self.right.process(right_samples, threshold, consecutive_samples);
+    }
+
+    pub fn get_left_count(&self) -> ClipCount {
+        self.left.count
+    }
+
+    pub fn get_right_count(&self) -> ClipCount {
+        self.right.count
+    }
+
+    pub fn update_settings(&mut self, settings: ClipSettings) {
+        self.settings = settings;
+    }
+
+    pub fn reset(&mut self) {
+        self.left = ChannelClipDetector::default();
+        self.right = ChannelClipDetector::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_one_over_for_each_run_of_consecutive_samples_above_the_threshold() {
+        let mut detector = ClipDetector::default();
+        let samples = [0.5, 0.995, -0.995, 0.995, 0.995, 0.5, 0.995, 0.995, 0.5];
+
+        detector.process(&samples, &[]);
+
+        assert_eq!(detector.get_left_count().overs, 1);
+        assert_eq!(detector.get_left_count().full_scale_samples, 0);
+        assert!(!detector.get_right_count().is_clipped());
+    }
+
+    #[test]
+    fn count_runs_that_span_separate_buffers() {
+        let mut detector = ClipDetector::default();
+
+        detector.process(&[0.0, 1.0, 1.0], &[0.0]);
+        assert_eq!(detector.get_left_count().overs, 0);
+
+        detector.process(&[-1.0, 0.0], &[0.0]);
+        assert_eq!(
+            detector.get_left_count(),
+            ClipCount {
+                overs: 1,
+                full_scale_samples: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn use_the_configured_threshold_and_run_length() {
+        let mut detector = ClipDetector::default();
+        detector.update_settings(ClipSettings {
+            threshold_dbfs: -6.0,
+            consecutive_samples: 1,
+        });
+
+        detector.process(&[0.4, 0.6, 0.0, 0.6], &[0.6]);
+
+        assert_eq!(detector.get_left_count().overs, 2);
+        assert_eq!(detector.get_right_count().overs, 1);
+    }
+
+    #[test]
+    fn counts_stay_latched_until_reset() {
+        let mut detector = ClipDetector::default();
+        detector.process(&[1.0, 1.0, 1.0], &[1.0]);
+        detector.process(&[0.0; 16], &[0.0; 16]);
+        assert!(detector.get_left_count().is_clipped());
+        assert!(detector.get_right_count().is_clipped());
+
+        detector.reset();
+        assert!(!detector.get_left_count().is_clipped());
+        assert!(!detector.get_right_count().is_clipped());
+    }
+}
@@ -1,7 +1,11 @@
+mod analysis;
+mod calibration;
 mod device_manager;
 mod errors;
 mod events;
 pub mod level_meter;
+mod measurement;
+mod settings;
 pub mod tone_generator;
 mod ui;
 
@@ -10,37 +14,68 @@ use crate::errors::{EXIT_CODE_ERROR, LocalError, handle_local_error};
 use crate::events::EventType;
 use crate::events::Events;
 use crate::level_meter::LevelMeter;
-use crate::tone_generator::ToneGenerator;
+use crate::measurement::MeasurementManager;
+use crate::settings::preset::{
+    apply_preset, get_preset_argument, get_preset_file_path_from_argument, get_presets_directory,
+    read_preset_file,
+};
+use crate::settings::{Settings, get_settings_file_path, read_settings_file};
+use crate::tone_generator::{ToneGenerator, create_burst_played_ring_buffer};
+use crate::tone_generator::sweep::{SharedSweepFrequency, SweepSettings};
 use crate::ui::UI;
 use crossbeam_channel::{Receiver, Sender};
 use slint::ComponentHandle;
+use std::env;
 use std::process::exit;
 use std::sync::{Arc, Mutex};
 use std::thread;
 
 slint::include_modules!();
 
-const DEFAULT_REFERENCE_FREQUENCY: f32 = 1000.0;
-const DEFAULT_REFERENCE_LEVEL: i32 = -18;
-pub const DEFAULT_DELTA_MODE: bool = true;
-
 fn main() -> Result<(), slint::PlatformError> {
     // Initialize Slint Application
     let application = AppWindow::new()?;
 
+    // Restore the settings saved when the application last closed
+    let mut settings = match get_settings_file_path().map(|file_path| read_settings_file(&file_path)) {
+        Some(Ok(settings)) => settings,
+        Some(Err(error)) => {
+            handle_local_error(&error, "");
+            Settings::default()
+        }
+        None => Settings::default(),
+    };
+
+    // A preset named on the command line overrides the saved settings
+    if let Some(preset_argument) = get_preset_argument(env::args().skip(1)) {
+        match get_preset_file_path_from_argument(get_presets_directory().as_deref(), &preset_argument)
+            .and_then(|file_path| read_preset_file(&file_path))
+        {
+            Ok(preset) => apply_preset(&mut settings, &preset),
+            Err(error) => handle_local_error(&error, ""),
+        }
+    }
+
     // Initialize Events Module
     let events = Events::new();
 
+    // Share what the output callback plays without sending from the real-time thread
+    let (burst_played_producer, burst_played_consumer) = create_burst_played_ring_buffer();
+    let sweep_frequency = SharedSweepFrequency::default();
+
     // Initialize UI Module
     let tone_generator_sender = events.get_tone_generator_sender();
     let level_meter_sender = events.get_level_meter_sender();
     let user_interface_sender = events.get_user_interface_sender();
+    let measurement_sender = events.get_measurement_sender();
 
     let mut ui =UI::new(
         &Arc::new(Mutex::new(application.as_weak())),
         tone_generator_sender,
         level_meter_sender,
         user_interface_sender,
+        measurement_sender,
+        sweep_frequency.clone(),
     );
 
     ui.create_ui_callbacks();
@@ -54,6 +89,7 @@ fn main() -> Result<(), slint::PlatformError> {
         device_manager_ui_sender,
         level_meter_sender,
         tone_generator_sender,
+        &settings,
     ) {
         Ok(device_manager) => device_manager,
         Err(error) => {
@@ -65,9 +101,8 @@ fn main() -> Result<(), slint::PlatformError> {
     ui.initialize_ui_with_device_data(
         device_manager.get_initial_input_device(),
         device_manager.get_initial_output_device(),
-        DEFAULT_REFERENCE_FREQUENCY,
-        DEFAULT_REFERENCE_LEVEL,
-        DEFAULT_DELTA_MODE,
+        &settings,
+        SweepSettings::default(),
     );
 
     // Initialize Tone Generator Module
@@ -76,10 +111,12 @@ fn main() -> Result<(), slint::PlatformError> {
 
     thread::spawn(move || {
         let mut tone_generator = match ToneGenerator::new(
-            DEFAULT_REFERENCE_FREQUENCY,
-            DEFAULT_REFERENCE_LEVEL as f32,
+            settings.reference_frequency,
+            settings.reference_level,
             tone_generator_receiver,
             tone_generator_ui_sender,
+            burst_played_producer,
+            sweep_frequency,
         ) {
             Ok(tone_generator) => tone_generator,
             Err(error) => {
@@ -97,6 +134,7 @@ fn main() -> Result<(), slint::PlatformError> {
     // Initialize Level Meter Module
     let level_meter_ui_sender: Sender<EventType> = events.get_user_interface_sender();
     let level_meter_receiver = events.get_level_meter_receiver();
+    let level_meter_measurement_sender = events.get_measurement_sender();
 
     thread::spawn(move || {
         let mut level_meter = match LevelMeter::new(level_meter_receiver) {
@@ -110,7 +148,9 @@ fn main() -> Result<(), slint::PlatformError> {
             }
         };
 
-        if let Err(error) = level_meter.run_input_sample_processor(level_meter_ui_sender) {
+        if let Err(error) = level_meter
+            .run_input_sample_processor(level_meter_ui_sender, level_meter_measurement_sender)
+        {
             handle_local_error(
                 &LocalError::LevelMeterInitialization(error.to_string()),
                 "",
@@ -127,6 +167,22 @@ fn main() -> Result<(), slint::PlatformError> {
         }
     });
 
+    // Initialize Measurement Manager Module
+    let mut measurement_manager = MeasurementManager::new(
+        events.get_measurement_receiver(),
+        burst_played_consumer,
+        events.get_tone_generator_sender(),
+        events.get_level_meter_sender(),
+        events.get_user_interface_sender(),
+    );
+
+    thread::spawn(move || {
+        if let Err(error) = measurement_manager.run() {
+            handle_local_error(&LocalError::MeasurementInitialization, &error.to_string());
+            exit(EXIT_CODE_ERROR);
+        }
+    });
+
     // Spawn the run loop for the Devices Manager module consuming the initialized object
     thread::spawn(move || {
         if let Err(error) = device_manager.run() {
@@ -0,0 +1,250 @@
+use crate::analysis::CapturedSamples;
+use crate::errors::LocalError;
+use crate::events::EventType;
+use crate::tone_generator::{OutputChannels, Waveform};
+use crossbeam_channel::{Receiver, Sender};
+use crosstalk::{CrosstalkRoutine, CrosstalkSettings};
+use latency::{LatencyRoutine, LatencySettings};
+use frequency_response::{FrequencyResponseRoutine, FrequencyResponseSettings};
+use gain_drift::{GainDriftRoutine, GainDriftSettings};
+use noise_floor::{NoiseFloorRoutine, NoiseFloorSettings};
+use polarity::{PolarityRoutine, PolaritySettings};
+use rtrb::Consumer;
+use std::error::Error;
+use std::time::{Duration, Instant};
+use unity_check::{UnityCheckRoutine, UnityCheckSettings};
+
+mod burst_capture;
+pub mod crosstalk;
+pub mod frequency_response;
+pub mod gain_drift;
+pub mod latency;
+pub mod noise_floor;
+pub mod polarity;
+mod stepped_tones;
+pub mod unity_check;
+
+const BURST_PLAYED_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MeasurementRequest {
+    FrequencyResponse(FrequencyResponseSettings),
+    UnityCheck(UnityCheckSettings),
+    NoiseFloor(NoiseFloorSettings),
+    Crosstalk(CrosstalkSettings),
+    Latency(LatencySettings),
+    Polarity(PolaritySettings),
+    GainDrift(GainDriftSettings),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MeasurementStatus {
+    Running,
+    Complete,
+}
+
+pub trait MeasurementRoutine: Send {
+    fn begin(&mut self, controller: &MeasurementController) -> Result<(), LocalError>;
+    fn process_captured_samples(
+        &mut self,
+        samples: &CapturedSamples,
+        controller: &MeasurementController,
+    ) -> Result<MeasurementStatus, LocalError>;
+    fn process_burst_played(&mut self, _played_at: Instant) {}
+}
+
+pub struct MeasurementController {
+    tone_generator_sender: Sender<EventType>,
+    level_meter_sender: Sender<EventType>,
+    user_interface_sender: Sender<EventType>,
+}
+
+impl MeasurementController {
+    pub fn set_tone_frequency(&self, frequency: f32) -> Result<(), LocalError> {
+        self.send_to_tone_generator(EventType::ToneFrequencyUpdate(frequency))
+    }
+
+    pub fn set_tone_waveform(&self, waveform: Waveform) -> Result<(), LocalError> {
+        self.send_to_tone_generator(EventType::ToneWaveformUpdate(waveform))
+    }
+
+    pub fn set_tone_output_channels(&self, output_channels: OutputChannels) -> Result<(), LocalError> {
+        self.send_to_tone_generator(EventType::ToneOutputChannelsUpdate(output_channels))
+    }
+
+    pub fn trigger_tone_burst(&self) -> Result<(), LocalError> {
+        self.send_to_tone_generator(EventType::ToneBurstTrigger)
+    }
+
+    pub fn start_tone_and_meter(&self) -> Result<(), LocalError> {
+        self.start_tone()?;
+        self.start_meter()
+    }
+
+    pub fn start_tone(&self) -> Result<(), LocalError> {
+        self.send_to_tone_generator(EventType::Start)
+    }
+
+    pub fn stop_tone(&self) -> Result<(), LocalError> {
+        self.send_to_tone_generator(EventType::Stop)
+    }
+
+    pub fn start_meter(&self) -> Result<(), LocalError> {
+        self.level_meter_sender
+            .send(EventType::Start)
+            .map_err(|err| LocalError::MeasurementSender(err.to_string()))
+    }
+
+    pub fn set_meter_capture_active(&self, active: bool) -> Result<(), LocalError> {
+        self.level_meter_sender
+            .send(EventType::MeterMeasurementCaptureUpdate(active))
+            .map_err(|err| LocalError::MeasurementSender(err.to_string()))
+    }
+
+    pub fn stop_tone_and_meter(&self) -> Result<(), LocalError> {
+        self.stop_tone()?;
+        self.level_meter_sender
+            .send(EventType::Stop)
+            .map_err(|err| LocalError::MeasurementSender(err.to_string()))
+    }
+
+    pub fn report_progress(&self, message: String) -> Result<(), LocalError> {
+        self.send_to_ui(EventType::MeasurementProgressUpdate(message))
+    }
+
+    pub fn send_to_ui(&self, event: EventType) -> Result<(), LocalError> {
+        self.user_interface_sender
+            .send(event)
+            .map_err(|err| LocalError::MeasurementSender(err.to_string()))
+    }
+
+    fn send_to_tone_generator(&self, event: EventType) -> Result<(), LocalError> {
+        self.tone_generator_sender
+            .send(event)
+            .map_err(|err| LocalError::MeasurementSender(err.to_string()))
+    }
+}
+
+pub struct MeasurementManager {
+    measurement_receiver: Receiver<EventType>,
+    burst_played_consumer: Consumer<Instant>,
+    controller: MeasurementController,
+    active_routine: Option<Box<dyn MeasurementRoutine>>,
+}
+
+impl MeasurementManager {
+    pub fn new(
+        measurement_receiver: Receiver<EventType>,
+        burst_played_consumer: Consumer<Instant>,
+        tone_generator_sender: Sender<EventType>,
+        level_meter_sender: Sender<EventType>,
+        user_interface_sender: Sender<EventType>,
+    ) -> Self {
+        Self {
+            measurement_receiver,
+            burst_played_consumer,
+            controller: MeasurementController {
+                tone_generator_sender,
+                level_meter_sender,
+                user_interface_sender,
+            },
+            active_routine: None,
+        }
+    }
+
+    pub fn run(&mut self) -> Result<(), Box<dyn Error>> {
+        let measurement_receiver = self.measurement_receiver.clone();
+
+        loop {
+            self.process_played_bursts();
+
+            if let Ok(event) = measurement_receiver.recv_timeout(BURST_PLAYED_POLL_INTERVAL) {
+                match event {
+                    EventType::MeasurementStart(request) => self.start_measurement(request)?,
+                    EventType::MeasurementStop => self.finish_measurement()?,
+                    EventType::MeasurementSamples(samples) => {
+                        self.process_captured_samples(&samples)?;
+                    }
+                    EventType::Exit => break,
+                    _ => (),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn start_measurement(&mut self, request: MeasurementRequest) -> Result<(), LocalError> {
+        if self.active_routine.is_some() {
+            self.finish_measurement()?;
+        }
+
+        let mut routine = get_routine_from_measurement_request(request);
+
+        self.controller
+            .send_to_ui(EventType::MeasurementRunningUpdate(true))?;
+        self.controller.set_meter_capture_active(true)?;
+
+        let begin_result = routine.begin(&self.controller);
+        self.active_routine = Some(routine);
+
+        if let Err(error) = begin_result {
+            return self.abort_measurement(&error);
+        }
+
+        Ok(())
+    }
+
+    fn process_played_bursts(&mut self) {
+        while let Ok(played_at) = self.burst_played_consumer.pop() {
+            if let Some(routine) = self.active_routine.as_mut() {
+                routine.process_burst_played(played_at);
+            }
+        }
+    }
+
+    fn process_captured_samples(&mut self, samples: &CapturedSamples) -> Result<(), LocalError> {
+        let Some(routine) = self.active_routine.as_mut() else {
+            return Ok(());
+        };
+
+        match routine.process_captured_samples(samples, &self.controller) {
+            Ok(MeasurementStatus::Running) => Ok(()),
+            Ok(MeasurementStatus::Complete) => self.finish_measurement(),
+            Err(error) => self.abort_measurement(&error),
+        }
+    }
+
+    fn abort_measurement(&mut self, error: &LocalError) -> Result<(), LocalError> {
+        self.finish_measurement()?;
+        self.controller
+            .send_to_ui(EventType::RecoverableError(error.to_string()))
+    }
+
+    fn finish_measurement(&mut self) -> Result<(), LocalError> {
+        if self.active_routine.take().is_none() {
+            return Ok(());
+        }
+
+        self.controller.set_meter_capture_active(false)?;
+        self.controller.stop_tone_and_meter()?;
+        self.controller
+            .send_to_ui(EventType::MeasurementRunningUpdate(false))
+    }
+}
+
+fn get_routine_from_measurement_request(
+    request: MeasurementRequest,
+) -> Box<dyn MeasurementRoutine> {
+    match request {
+        MeasurementRequest::FrequencyResponse(settings) => {
+            Box::new(FrequencyResponseRoutine::new(settings))
+        }
+        MeasurementRequest::UnityCheck(settings) => Box::new(UnityCheckRoutine::new(settings)),
+        MeasurementRequest::NoiseFloor(settings) => Box::new(NoiseFloorRoutine::new(settings)),
+        MeasurementRequest::Crosstalk(settings) => Box::new(CrosstalkRoutine::new(settings)),
+        MeasurementRequest::Latency(settings) => Box::new(LatencyRoutine::new(settings)),
+        MeasurementRequest::Polarity(settings) => Box::new(PolarityRoutine::new(settings)),
+        MeasurementRequest::GainDrift(settings) => Box::new(GainDriftRoutine::new(settings)),
+    }
+}
@@ -0,0 +1,220 @@
+use crate::analysis::CapturedSamples;
+use crate::analysis::cross_correlation::{CorrelationPeak, get_cross_correlation_peak};
+use crate::errors::LocalError;
+use crate::tone_generator::burst::get_maximum_length_sequence;
+use std::time::Instant;
+
+const MAXIMUM_LATENCY_IN_SECONDS: f64 = 1.0;
+const MINIMUM_PEAK_TO_AVERAGE_RATIO: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BurstArrival {
+    pub latency_in_samples: f32,
+    pub interpolated_lag: f32,
+    pub is_inverted: bool,
+}
+
+pub struct BurstCapture {
+    sequence: Vec<f32>,
+    burst_played_at: Option<Instant>,
+    capture_started_at: Option<Instant>,
+    sample_rate: f32,
+    left: Vec<f32>,
+    right: Vec<f32>,
+}
+
+impl BurstCapture {
+    pub fn new() -> Self {
+        Self {
+            sequence: get_maximum_length_sequence(),
+            burst_played_at: None,
+            capture_started_at: None,
+            sample_rate: 0.0,
+            left: Vec::new(),
+            right: Vec::new(),
+        }
+    }
+
+    pub fn set_burst_played_at(&mut self, played_at: Instant) {
+        self.burst_played_at = Some(played_at);
+    }
+
+    fn has_right_channel(&self) -> bool {
+        !self.right.is_empty()
+    }
+
+    pub fn capture(&mut self, samples: &CapturedSamples) -> Result<bool, LocalError> {
+        let Some(burst_played_at) = self.burst_played_at else {
+            return Ok(false);
+        };
+
+        let Some(captured_at) = samples.captured_at else {
+            return Err(LocalError::MeasurementConfiguration(
+                "Captured samples have no timestamp".to_string(),
+            ));
+        };
+
+        let block_duration = samples.left.len() as f64 / f64::from(samples.sample_rate);
+        if get_signed_seconds_between(burst_played_at, captured_at) + block_duration <= 0.0 {
+            return Ok(false);
+        }
+
+        if self.left.is_empty() {
+            self.capture_started_at = Some(captured_at);
+            self.sample_rate = samples.sample_rate;
+        }
+        self.left.extend_from_slice(&samples.left);
+        self.right.extend_from_slice(&samples.right);
+
+        let capture_offset = self
+            .capture_started_at
+            .map_or(0.0, |started_at| get_signed_seconds_between(started_at, burst_played_at));
+        let required_duration = capture_offset
+            + MAXIMUM_LATENCY_IN_SECONDS
+            + self.sequence.len() as f64 / f64::from(self.sample_rate);
+
+        Ok(self.left.len() as f64 / f64::from(self.sample_rate) >= required_duration)
+    }
+
+    pub fn get_left_arrival(&self) -> Result<BurstArrival, LocalError> {
+        self.get_arrival(&self.left)
+    }
+
+    pub fn get_right_arrival(&self) -> Result<Option<BurstArrival>, LocalError> {
+        if !self.has_right_channel() {
+            return Ok(None);
+        }
+        self.get_arrival(&self.right).map(Some)
+    }
+
+    pub fn sample_rate(&self) -> f32 {
+        self.sample_rate
+    }
+
+    pub fn reset(&mut self) {
+        self.burst_played_at = None;
+        self.capture_started_at = None;
+        self.left.clear();
+        self.right.clear();
+    }
+
+    fn get_arrival(&self, captured_samples: &[f32]) -> Result<BurstArrival, LocalError> {
+        let (Some(capture_started_at), Some(burst_played_at)) =
+            (self.capture_started_at, self.burst_played_at)
+        else {
+            return Err(get_no_burst_error());
+        };
+
+        let peak: CorrelationPeak = get_cross_correlation_peak(captured_samples, &self.sequence)
+            .filter(|peak| peak.peak_to_average_ratio >= MINIMUM_PEAK_TO_AVERAGE_RATIO)
+            .ok_or_else(get_no_burst_error)?;
+
+        let burst_offset_in_samples = get_signed_seconds_between(capture_started_at, burst_played_at)
+            * f64::from(self.sample_rate);
+
+        Ok(BurstArrival {
+            latency_in_samples: (f64::from(peak.interpolated_lag) - burst_offset_in_samples) as f32,
+            interpolated_lag: peak.interpolated_lag,
+            is_inverted: peak.is_inverted,
+        })
+    }
+}
+
+fn get_no_burst_error() -> LocalError {
+    LocalError::MeasurementNoSignal("the test burst".to_string())
+}
+
+fn get_signed_seconds_between(from: Instant, to: Instant) -> f64 {
+    match to.checked_duration_since(from) {
+        Some(duration) => duration.as_secs_f64(),
+        None => -from.duration_since(to).as_secs_f64(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn get_capture_with_burst_at(left_index: usize, right_index: usize, right_gain: f32) -> BurstCapture {
+        let mut burst_capture = BurstCapture::new();
+        let length = left_index.max(right_index) + burst_capture.sequence.len() + 1000;
+        let mut left = vec![0.0; length];
+        let mut right = vec![0.0; length];
+
+        for (index, sample) in burst_capture.sequence.iter().enumerate() {
+            left[left_index + index] = 0.5 * sample;
+            right[right_index + index] = right_gain * sample;
+        }
+
+        let capture_started_at = Instant::now();
+        burst_capture.capture_started_at = Some(capture_started_at);
+        burst_capture.burst_played_at = Some(capture_started_at + Duration::from_millis(100));
+        burst_capture.sample_rate = 48000.0;
+        burst_capture.left = left;
+        burst_capture.right = right;
+        burst_capture
+    }
+
+    #[test]
+    fn return_signed_seconds_between_instants() {
+        let earlier = Instant::now();
+        let later = earlier + Duration::from_millis(250);
+        assert!((get_signed_seconds_between(earlier, later) - 0.25).abs() < 1e-9);
+        assert!((get_signed_seconds_between(later, earlier) + 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn return_latency_of_burst_arriving_after_it_was_played() {
+        let burst_capture = get_capture_with_burst_at(4800 + 480, 4800 + 480, 0.5);
+        let arrival = burst_capture.get_left_arrival().unwrap();
+        assert!((arrival.latency_in_samples - 480.0).abs() < 0.5);
+        assert!(!arrival.is_inverted);
+    }
+
+    #[test]
+    fn latency_counts_from_the_first_captured_sample_not_the_block_delivery() {
+        let mut burst_capture = BurstCapture::new();
+        let block_length = 4800;
+        let first_sample_at = Instant::now();
+        burst_capture.set_burst_played_at(first_sample_at);
+
+        let mut first_block = vec![0.0; block_length + burst_capture.sequence.len()];
+        for (index, sample) in burst_capture.sequence.iter().enumerate() {
+            first_block[480 + index] = 0.5 * sample;
+        }
+
+        let mut captured_at = first_sample_at;
+        let mut left = first_block;
+        while !burst_capture
+            .capture(&CapturedSamples {
+                left: left.clone(),
+                right: Vec::new(),
+                sample_rate: 48000.0,
+                captured_at: Some(captured_at),
+            })
+            .unwrap()
+        {
+            captured_at += Duration::from_secs_f64(left.len() as f64 / 48000.0);
+            left = vec![0.0; block_length];
+        }
+
+        let arrival = burst_capture.get_left_arrival().unwrap();
+        assert!((arrival.latency_in_samples - 480.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn return_inverted_arrival_with_its_own_offset_on_the_right_channel() {
+        let burst_capture = get_capture_with_burst_at(5000, 5003, -0.5);
+        let left = burst_capture.get_left_arrival().unwrap();
+        let right = burst_capture.get_right_arrival().unwrap().unwrap();
+        assert!(right.is_inverted);
+        assert!((right.interpolated_lag - left.interpolated_lag - 3.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn return_error_when_no_burst_was_captured() {
+        let burst_capture = get_capture_with_burst_at(5000, 5000, 0.0);
+        assert!(burst_capture.get_right_arrival().is_err());
+    }
+}
@@ -0,0 +1,175 @@
+use crate::analysis::{CapturedSamples, get_tone_level_at_frequency};
+use crate::errors::LocalError;
+use crate::events::EventType;
+use crate::measurement::{MeasurementController, MeasurementRoutine, MeasurementStatus};
+use crate::tone_generator::{OutputChannels, Waveform};
+
+const SETTLING_BLOCKS_PER_SIDE: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrosstalkSettings {
+    pub reference_frequency: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelCrosstalk {
+    pub driven_level: f32,
+    pub leakage_level: f32,
+}
+
+impl ChannelCrosstalk {
+    pub fn get_crosstalk(&self) -> f32 {
+        self.leakage_level - self.driven_level
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CrosstalkResult {
+    pub left_driven: Option<ChannelCrosstalk>,
+    pub right_driven: Option<ChannelCrosstalk>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DrivenSide {
+    Left,
+    Right,
+}
+
+pub struct CrosstalkRoutine {
+    settings: CrosstalkSettings,
+    driven_side: DrivenSide,
+    settling_blocks_remaining: usize,
+    result: CrosstalkResult,
+}
+
+impl CrosstalkRoutine {
+    pub fn new(settings: CrosstalkSettings) -> Self {
+        Self {
+            settings,
+            driven_side: DrivenSide::Left,
+            settling_blocks_remaining: SETTLING_BLOCKS_PER_SIDE,
+            result: CrosstalkResult::default(),
+        }
+    }
+
+    fn get_channel_crosstalk(&self, samples: &CapturedSamples) -> Result<ChannelCrosstalk, LocalError> {
+        let frequency = self.settings.reference_frequency;
+        let (driven_samples, leakage_samples) = match self.driven_side {
+            DrivenSide::Left => (&samples.left, &samples.right),
+            DrivenSide::Right => (&samples.right, &samples.left),
+        };
+
+        let driven_level = get_tone_level_at_frequency(driven_samples, frequency, samples.sample_rate);
+        if !driven_level.is_finite() {
+            return Err(LocalError::MeasurementNoSignal(frequency.to_string()));
+        }
+
+        Ok(ChannelCrosstalk {
+            driven_level,
+            leakage_level: get_tone_level_at_frequency(leakage_samples, frequency, samples.sample_rate),
+        })
+    }
+}
+
+impl MeasurementRoutine for CrosstalkRoutine {
+    fn begin(&mut self, controller: &MeasurementController) -> Result<(), LocalError> {
+        controller.send_to_ui(EventType::CrosstalkUpdate(self.result))?;
+        controller.report_progress("Driving the left output".to_string())?;
+        controller.set_tone_waveform(Waveform::Sine)?;
+        controller.set_tone_frequency(self.settings.reference_frequency)?;
+        controller.set_tone_output_channels(OutputChannels::LeftOnly)?;
+        controller.start_tone_and_meter()
+    }
+
+    fn process_captured_samples(
+        &mut self,
+        samples: &CapturedSamples,
+        controller: &MeasurementController,
+    ) -> Result<MeasurementStatus, LocalError> {
+        if !samples.has_right_channel() {
+            return Err(LocalError::MeasurementConfiguration(
+                "Crosstalk needs both a left and a right input channel".to_string(),
+            ));
+        }
+
+        if self.settling_blocks_remaining > 0 {
+            self.settling_blocks_remaining -= 1;
+            return Ok(MeasurementStatus::Running);
+        }
+
+        let channel_crosstalk = self.get_channel_crosstalk(samples)?;
+
+        match self.driven_side {
+            DrivenSide::Left => {
+                self.result.left_driven = Some(channel_crosstalk);
+                controller.send_to_ui(EventType::CrosstalkUpdate(self.result))?;
+
+                self.driven_side = DrivenSide::Right;
+                self.settling_blocks_remaining = SETTLING_BLOCKS_PER_SIDE;
+                controller.report_progress("Driving the right output".to_string())?;
+                controller.set_tone_output_channels(OutputChannels::RightOnly)?;
+
+                Ok(MeasurementStatus::Running)
+            }
+            DrivenSide::Right => {
+                self.result.right_driven = Some(channel_crosstalk);
+                controller.send_to_ui(EventType::CrosstalkUpdate(self.result))?;
+                controller.report_progress("Crosstalk complete".to_string())?;
+
+                Ok(MeasurementStatus::Complete)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::generate_sine_samples;
+
+    #[test]
+    fn return_crosstalk_relative_to_the_driven_channel() {
+        let channel_crosstalk = ChannelCrosstalk {
+            driven_level: -18.0,
+            leakage_level: -108.0,
+        };
+        assert_eq!(channel_crosstalk.get_crosstalk(), -90.0);
+    }
+
+    #[test]
+    fn return_leakage_from_the_undriven_input_for_each_side() {
+        let mut routine = CrosstalkRoutine::new(CrosstalkSettings {
+            reference_frequency: 1000.0,
+        });
+        let samples = CapturedSamples {
+            left: generate_sine_samples(1000.0, 0.5, 0.0, 48000.0, 10240),
+            right: generate_sine_samples(1000.0, 0.0005, 0.0, 48000.0, 10240),
+            sample_rate: 48000.0,
+            captured_at: None,
+        };
+
+        let left_driven = routine.get_channel_crosstalk(&samples).unwrap();
+        assert!((left_driven.get_crosstalk() + 60.0).abs() < 0.01);
+
+        routine.driven_side = DrivenSide::Right;
+        let right_driven = routine.get_channel_crosstalk(&samples).unwrap();
+        assert!((right_driven.get_crosstalk() - 60.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn return_error_when_the_driven_channel_is_silent() {
+        let routine = CrosstalkRoutine::new(CrosstalkSettings {
+            reference_frequency: 1000.0,
+        });
+        let samples = CapturedSamples {
+            left: vec![0.0; 4096],
+            right: vec![0.0; 4096],
+            sample_rate: 48000.0,
+            captured_at: None,
+        };
+        assert_eq!(
+            routine.get_channel_crosstalk(&samples),
+            Err(LocalError::MeasurementNoSignal("1000".to_string()))
+        );
+    }
+}
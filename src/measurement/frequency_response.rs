@@ -0,0 +1,216 @@
+use crate::analysis::{CapturedSamples, get_tone_level_at_frequency};
+use crate::errors::LocalError;
+use crate::events::EventType;
+use crate::measurement::stepped_tones::{SteppedTones, get_log_spaced_frequencies};
+use crate::measurement::{MeasurementController, MeasurementRoutine, MeasurementStatus};
+use std::fs;
+use std::path::Path;
+
+const SETTLING_BLOCKS_PER_STEP: usize = 2;
+const FRD_FILE_HEADER: &str = "* Stereo Unity Gain frequency response\n* Freq(Hz) SPL(dB) Phase(degrees)\n";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrequencyResponseSettings {
+    pub start_frequency: f32,
+    pub stop_frequency: f32,
+    pub points_per_octave: u32,
+    pub reference_frequency: f32,
+}
+
+impl Default for FrequencyResponseSettings {
+    fn default() -> Self {
+        Self {
+            start_frequency: 20.0,
+            stop_frequency: 20000.0,
+            points_per_octave: 3,
+            reference_frequency: 1000.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrequencyResponsePoint {
+    pub frequency: f32,
+    pub left: f32,
+    pub right: Option<f32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrequencyResponse {
+    pub points: Vec<FrequencyResponsePoint>,
+}
+
+impl FrequencyResponse {
+    pub fn left_points(&self) -> Vec<(f32, f32)> {
+        self.points
+            .iter()
+            .map(|point| (point.frequency, point.left))
+            .collect()
+    }
+
+    pub fn right_points(&self) -> Vec<(f32, f32)> {
+        self.points
+            .iter()
+            .filter_map(|point| point.right.map(|right| (point.frequency, right)))
+            .collect()
+    }
+
+    pub fn get_left_maximum_deviation(&self) -> Option<f32> {
+        get_maximum_deviation(&self.left_points())
+    }
+
+    pub fn get_right_maximum_deviation(&self) -> Option<f32> {
+        get_maximum_deviation(&self.right_points())
+    }
+}
+
+pub struct FrequencyResponseRoutine {
+    settings: FrequencyResponseSettings,
+    stepped_tones: SteppedTones,
+    reference_levels: Option<(f32, Option<f32>)>,
+    response: FrequencyResponse,
+}
+
+impl FrequencyResponseRoutine {
+    pub fn new(settings: FrequencyResponseSettings) -> Self {
+        let mut frequencies = vec![settings.reference_frequency];
+        frequencies.extend(get_log_spaced_frequencies(
+            settings.start_frequency,
+            settings.stop_frequency,
+            settings.points_per_octave,
+        ));
+
+        Self {
+            settings,
+            stepped_tones: SteppedTones::new(frequencies, SETTLING_BLOCKS_PER_STEP),
+            reference_levels: None,
+            response: FrequencyResponse::default(),
+        }
+    }
+}
+
+impl MeasurementRoutine for FrequencyResponseRoutine {
+    fn begin(&mut self, controller: &MeasurementController) -> Result<(), LocalError> {
+        self.reference_levels = None;
+        self.response = FrequencyResponse::default();
+        controller.send_to_ui(EventType::FrequencyResponseUpdate(self.response.clone()))?;
+        controller.report_progress(format!(
+            "Measuring reference at {} Hz",
+            self.settings.reference_frequency
+        ))?;
+        self.stepped_tones.begin(controller)
+    }
+
+    fn process_captured_samples(
+        &mut self,
+        samples: &CapturedSamples,
+        controller: &MeasurementController,
+    ) -> Result<MeasurementStatus, LocalError> {
+        if !self.stepped_tones.is_settled() {
+            return Ok(MeasurementStatus::Running);
+        }
+
+        let Some(frequency) = self.stepped_tones.current_frequency() else {
+            return Ok(MeasurementStatus::Complete);
+        };
+
+        let left_level = get_tone_level_at_frequency(&samples.left, frequency, samples.sample_rate);
+        let right_level = samples
+            .has_right_channel()
+            .then(|| get_tone_level_at_frequency(&samples.right, frequency, samples.sample_rate));
+
+        match self.reference_levels {
+            None => {
+                if !left_level.is_finite() {
+                    return Err(LocalError::MeasurementNoSignal(frequency.to_string()));
+                }
+                self.reference_levels = Some((left_level, right_level));
+            }
+            Some((left_reference, right_reference)) => {
+                self.response.points.push(FrequencyResponsePoint {
+                    frequency,
+                    left: left_level - left_reference,
+                    right: right_level.zip(right_reference).map(|(level, reference)| level - reference),
+                });
+                controller.send_to_ui(EventType::FrequencyResponseUpdate(self.response.clone()))?;
+            }
+        }
+
+        if !self.stepped_tones.advance(controller)? {
+            controller.report_progress("Frequency response complete".to_string())?;
+            return Ok(MeasurementStatus::Complete);
+        }
+
+        controller.report_progress(format!(
+            "Measuring step {} of {}",
+            self.stepped_tones.current_step_number() - 1,
+            self.stepped_tones.number_of_steps() - 1
+        ))?;
+
+        Ok(MeasurementStatus::Running)
+    }
+}
+
+pub fn write_frd_file(file_path: &Path, points: &[(f32, f32)]) -> Result<(), LocalError> {
+    fs::write(file_path, get_frd_text_from_points(points))
+        .map_err(|err| LocalError::MeasurementExport(err.to_string()))
+}
+
+fn get_frd_text_from_points(points: &[(f32, f32)]) -> String {
+    let mut frd_text = String::from(FRD_FILE_HEADER);
+    for (frequency, level) in points {
+        frd_text.push_str(&format!("{frequency:.3} {level:.4} 0.0\n"));
+    }
+    frd_text
+}
+
+fn get_maximum_deviation(points: &[(f32, f32)]) -> Option<f32> {
+    points
+        .iter()
+        .map(|(_, level)| level.abs())
+        .reduce(f32::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn return_frd_text_with_one_line_per_point() {
+        let points = [(20.0, -0.25), (1000.0, 0.0)];
+        let frd_text = get_frd_text_from_points(&points);
+        let expected_text = format!("{FRD_FILE_HEADER}20.000 -0.2500 0.0\n1000.000 0.0000 0.0\n");
+        assert_eq!(frd_text, expected_text);
+    }
+
+    #[test]
+    fn return_largest_absolute_deviation_from_reference() {
+        let points = [(20.0, -0.3), (1000.0, 0.0), (20000.0, 0.1)];
+        assert_eq!(get_maximum_deviation(&points), Some(0.3));
+    }
+
+    #[test]
+    fn return_no_deviation_when_there_are_no_points() {
+        assert_eq!(get_maximum_deviation(&[]), None);
+    }
+
+    #[test]
+    fn return_only_measured_right_channel_points() {
+        let response = FrequencyResponse {
+            points: vec![
+                FrequencyResponsePoint {
+                    frequency: 100.0,
+                    left: 0.1,
+                    right: None,
+                },
+                FrequencyResponsePoint {
+                    frequency: 200.0,
+                    left: 0.2,
+                    right: Some(-0.2),
+                },
+            ],
+        };
+        assert_eq!(response.right_points(), vec![(200.0, -0.2)]);
+        assert_eq!(response.get_left_maximum_deviation(), Some(0.2));
+    }
+}
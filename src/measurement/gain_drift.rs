@@ -0,0 +1,382 @@
+use crate::analysis::CapturedSamples;
+use crate::errors::LocalError;
+use crate::events::EventType;
+use crate::level_meter::get_peak_value_of_collected_samples;
+use crate::measurement::{MeasurementController, MeasurementRoutine, MeasurementStatus};
+use crate::tone_generator::Waveform;
+use std::cmp::Ordering;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const SETTLING_BLOCKS: usize = 2;
+const CSV_FILE_HEADER: &str = "timestamp_unix_seconds,elapsed_seconds,left_delta_db,right_delta_db\n";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GainDriftSettings {
+    pub reference_frequency: f32,
+    pub reference_level: f32,
+    pub interval_in_seconds: u32,
+    pub log_file_path: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GainDriftPoint {
+    pub elapsed_in_seconds: f64,
+    pub left_delta: f32,
+    pub right_delta: Option<f32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GainDriftLog {
+    pub points: Vec<GainDriftPoint>,
+}
+
+impl GainDriftLog {
+    pub fn left_points(&self) -> Vec<(f32, f32)> {
+        self.points
+            .iter()
+            .map(|point| (point.elapsed_in_seconds as f32, point.left_delta))
+            .collect()
+    }
+
+    pub fn right_points(&self) -> Vec<(f32, f32)> {
+        self.points
+            .iter()
+            .filter_map(|point| {
+                point
+                    .right_delta
+                    .map(|right| (point.elapsed_in_seconds as f32, right))
+            })
+            .collect()
+    }
+
+    pub fn get_elapsed_in_seconds(&self) -> f64 {
+        self.points
+            .last()
+            .map(|point| point.elapsed_in_seconds)
+            .unwrap_or_default()
+    }
+
+    pub fn get_left_drift(&self) -> Option<f32> {
+        get_drift(&self.left_points())
+    }
+
+    pub fn get_right_drift(&self) -> Option<f32> {
+        get_drift(&self.right_points())
+    }
+
+    pub fn decimate(&mut self, number_of_columns: usize) {
+        let elapsed_in_seconds = self.get_elapsed_in_seconds();
+        let Some(last_point) = self.points.last().copied() else {
+            return;
+        };
+        if number_of_columns == 0 || elapsed_in_seconds <= 0.0 {
+            return;
+        }
+
+        let get_column = |point: &GainDriftPoint| {
+            ((point.elapsed_in_seconds / elapsed_in_seconds * number_of_columns as f64) as usize)
+                .min(number_of_columns - 1)
+        };
+
+        self.points = self
+            .points
+            .chunk_by(|a, b| get_column(a) == get_column(b))
+            .flat_map(|column_points| {
+                get_extreme_point_indexes(column_points)
+                    .into_iter()
+                    .map(|index| column_points[index])
+            })
+            .collect();
+
+        if self.points.last() != Some(&last_point) {
+            self.points.push(last_point);
+        }
+    }
+}
+
+pub struct GainDriftRoutine {
+    settings: GainDriftSettings,
+    settling_blocks_remaining: usize,
+    started_at: Option<Instant>,
+    started_at_unix_seconds: f64,
+    next_point_at: Option<Instant>,
+    number_of_points: usize,
+}
+
+impl GainDriftRoutine {
+    pub fn new(settings: GainDriftSettings) -> Self {
+        Self {
+            settings,
+            settling_blocks_remaining: SETTLING_BLOCKS,
+            started_at: None,
+            started_at_unix_seconds: 0.0,
+            next_point_at: None,
+            number_of_points: 0,
+        }
+    }
+
+    fn get_point_when_due(&mut self, samples: &CapturedSamples) -> Option<GainDriftPoint> {
+        let captured_at = samples.captured_at.unwrap_or_else(Instant::now);
+        let started_at = *self.started_at.get_or_insert(captured_at);
+
+        if self
+            .next_point_at
+            .is_some_and(|next_point_at| captured_at < next_point_at)
+        {
+            return None;
+        }
+
+        let interval = Duration::from_secs(u64::from(self.settings.interval_in_seconds.max(1)));
+        self.next_point_at = Some(self.next_point_at.unwrap_or(captured_at) + interval);
+
+        let reference_level = self.settings.reference_level;
+        Some(GainDriftPoint {
+            elapsed_in_seconds: captured_at.duration_since(started_at).as_secs_f64(),
+            left_delta: get_peak_value_of_collected_samples(&samples.left) - reference_level,
+            right_delta: samples
+                .has_right_channel()
+                .then(|| get_peak_value_of_collected_samples(&samples.right) - reference_level),
+        })
+    }
+}
+
+impl MeasurementRoutine for GainDriftRoutine {
+    fn begin(&mut self, controller: &MeasurementController) -> Result<(), LocalError> {
+        self.started_at_unix_seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs_f64())
+            .unwrap_or_default();
+
+        create_gain_drift_csv_file(&self.settings.log_file_path)?;
+
+        controller.send_to_ui(EventType::GainDriftStart)?;
+        controller.report_progress(format!(
+            "Logging gain drift every {} seconds",
+            self.settings.interval_in_seconds
+        ))?;
+        controller.set_tone_waveform(Waveform::Sine)?;
+        controller.set_tone_frequency(self.settings.reference_frequency)?;
+        controller.start_tone_and_meter()
+    }
+
+    fn process_captured_samples(
+        &mut self,
+        samples: &CapturedSamples,
+        controller: &MeasurementController,
+    ) -> Result<MeasurementStatus, LocalError> {
+        if self.settling_blocks_remaining > 0 {
+            self.settling_blocks_remaining -= 1;
+            return Ok(MeasurementStatus::Running);
+        }
+
+        if let Some(point) = self.get_point_when_due(samples) {
+            append_point_to_gain_drift_csv_file(
+                &self.settings.log_file_path,
+                self.started_at_unix_seconds,
+                &point,
+            )?;
+            self.number_of_points += 1;
+
+            controller.send_to_ui(EventType::GainDriftUpdate(point))?;
+            controller.report_progress(format!(
+                "Logged {} points over {}",
+                self.number_of_points,
+                format_elapsed_time(point.elapsed_in_seconds)
+            ))?;
+        }
+
+        Ok(MeasurementStatus::Running)
+    }
+}
+
+pub fn export_gain_drift_csv_file(log_file_path: &Path, export_file_path: &Path) -> Result<(), LocalError> {
+    if log_file_path == export_file_path {
+        return Ok(());
+    }
+
+    fs::copy(log_file_path, export_file_path)
+        .map(|_| ())
+        .map_err(|err| LocalError::MeasurementExport(err.to_string()))
+}
+
+pub fn format_elapsed_time(elapsed_in_seconds: f64) -> String {
+    let total_seconds = elapsed_in_seconds.max(0.0) as u64;
+    format!(
+        "{}:{:02}:{:02}",
+        total_seconds / 3600,
+        (total_seconds / 60) % 60,
+        total_seconds % 60
+    )
+}
+
+fn create_gain_drift_csv_file(file_path: &Path) -> Result<(), LocalError> {
+    if let Some(directory) = file_path.parent() {
+        fs::create_dir_all(directory).map_err(|err| LocalError::MeasurementExport(err.to_string()))?;
+    }
+
+    fs::write(file_path, CSV_FILE_HEADER).map_err(|err| LocalError::MeasurementExport(err.to_string()))
+}
+
+fn append_point_to_gain_drift_csv_file(
+    file_path: &Path,
+    started_at_unix_seconds: f64,
+    point: &GainDriftPoint,
+) -> Result<(), LocalError> {
+    OpenOptions::new()
+        .append(true)
+        .open(file_path)
+        .and_then(|mut file| file.write_all(get_csv_line_from_point(started_at_unix_seconds, point).as_bytes()))
+        .map_err(|err| LocalError::MeasurementExport(err.to_string()))
+}
+
+fn get_csv_line_from_point(started_at_unix_seconds: f64, point: &GainDriftPoint) -> String {
+    let right_delta = point
+        .right_delta
+        .map(|right| format!("{right:.2}"))
+        .unwrap_or_default();
+    format!(
+        "{:.3},{:.3},{:.2},{right_delta}\n",
+        started_at_unix_seconds + point.elapsed_in_seconds,
+        point.elapsed_in_seconds,
+        point.left_delta
+    )
+}
+
+fn get_extreme_point_indexes(points: &[GainDriftPoint]) -> Vec<usize> {
+    let get_index = |get_delta: fn(&GainDriftPoint) -> Option<f32>, ordering: Ordering| {
+        points
+            .iter()
+            .enumerate()
+            .filter_map(|(index, point)| Some((index, get_delta(point)?)))
+            .reduce(|extreme, candidate| {
+                if candidate.1.total_cmp(&extreme.1) == ordering {
+                    candidate
+                } else {
+                    extreme
+                }
+            })
+            .map(|(index, _)| index)
+    };
+
+    let mut indexes: Vec<usize> = [
+        get_index(|point| Some(point.left_delta), Ordering::Less),
+        get_index(|point| Some(point.left_delta), Ordering::Greater),
+        get_index(|point| point.right_delta, Ordering::Less),
+        get_index(|point| point.right_delta, Ordering::Greater),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    indexes.sort_unstable();
+    indexes.dedup();
+    indexes
+}
+
+fn get_drift(points: &[(f32, f32)]) -> Option<f32> {
+    let minimum = points.iter().map(|(_, delta)| *delta).reduce(f32::min)?;
+    let maximum = points.iter().map(|(_, delta)| *delta).reduce(f32::max)?;
+    Some(maximum - minimum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_samples_at(captured_at: Instant, left_peak: f32) -> CapturedSamples {
+        CapturedSamples {
+            left: vec![left_peak, 0.0],
+            right: Vec::new(),
+            sample_rate: 48000.0,
+            captured_at: Some(captured_at),
+        }
+    }
+
+    #[test]
+    fn record_one_point_per_interval() {
+        let mut routine = GainDriftRoutine::new(GainDriftSettings {
+            reference_frequency: 1000.0,
+            reference_level: -6.0,
+            interval_in_seconds: 10,
+            log_file_path: PathBuf::new(),
+        });
+        let start = Instant::now();
+
+        let points: Vec<GainDriftPoint> = [0, 4, 9, 10, 15, 21]
+            .into_iter()
+            .filter_map(|seconds| {
+                routine.get_point_when_due(&get_samples_at(start + Duration::from_secs(seconds), 0.5))
+            })
+            .collect();
+
+        let elapsed: Vec<f64> = points.iter().map(|point| point.elapsed_in_seconds).collect();
+        assert_eq!(elapsed, vec![0.0, 10.0, 21.0]);
+        assert!((points[0].left_delta + 0.0206).abs() < 0.001);
+        assert_eq!(points[0].right_delta, None);
+    }
+
+    #[test]
+    fn return_drift_as_the_spread_of_the_deltas() {
+        let log = GainDriftLog {
+            points: vec![
+                GainDriftPoint {
+                    elapsed_in_seconds: 0.0,
+                    left_delta: 0.05,
+                    right_delta: Some(-0.1),
+                },
+                GainDriftPoint {
+                    elapsed_in_seconds: 60.0,
+                    left_delta: -0.15,
+                    right_delta: Some(-0.1),
+                },
+            ],
+        };
+        assert!((log.get_left_drift().unwrap() - 0.2).abs() < 1e-6);
+        assert_eq!(log.get_right_drift(), Some(0.0));
+        assert_eq!(GainDriftLog::default().get_left_drift(), None);
+    }
+
+    #[test]
+    fn return_csv_line_with_the_timestamp_of_the_point() {
+        let point = GainDriftPoint {
+            elapsed_in_seconds: 30.0,
+            left_delta: 0.12,
+            right_delta: None,
+        };
+        assert_eq!(
+            get_csv_line_from_point(1_700_000_000.0, &point),
+            "1700000030.000,30.000,0.12,\n"
+        );
+    }
+
+    #[test]
+    fn decimating_keeps_the_extremes_of_each_column() {
+        let mut log = GainDriftLog {
+            points: (0..=100)
+                .map(|second| GainDriftPoint {
+                    elapsed_in_seconds: f64::from(second),
+                    left_delta: (second as f32 * 0.7).sin(),
+                    right_delta: Some((second as f32 * 0.3).cos()),
+                })
+                .collect(),
+        };
+        let left_drift = log.get_left_drift();
+        let right_drift = log.get_right_drift();
+
+        log.decimate(10);
+
+        assert!(log.points.len() <= 41);
+        assert_eq!(log.get_left_drift(), left_drift);
+        assert_eq!(log.get_right_drift(), right_drift);
+        assert_eq!(log.get_elapsed_in_seconds(), 100.0);
+    }
+
+    #[test]
+    fn format_elapsed_time_as_hours_minutes_and_seconds() {
+        assert_eq!(format_elapsed_time(0.0), "0:00:00");
+        assert_eq!(format_elapsed_time(93784.2), "26:03:04");
+    }
+}
@@ -0,0 +1,156 @@
+use crate::analysis::CapturedSamples;
+use crate::errors::LocalError;
+use crate::events::EventType;
+use crate::measurement::burst_capture::BurstCapture;
+use crate::measurement::{MeasurementController, MeasurementRoutine, MeasurementStatus};
+use crate::tone_generator::{OutputChannels, Waveform};
+use std::time::Instant;
+
+const SETTLING_BLOCKS_BEFORE_FIRST_BURST: usize = 2;
+const SETTLING_BLOCKS_BETWEEN_BURSTS: usize = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatencySettings {
+    pub number_of_bursts: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LatencyResult {
+    pub sample_rate: f32,
+    pub latencies_in_samples: Vec<f32>,
+}
+
+impl LatencyResult {
+    pub fn get_mean(&self) -> Option<f32> {
+        if self.latencies_in_samples.is_empty() {
+            return None;
+        }
+        Some(self.latencies_in_samples.iter().sum::<f32>() / self.latencies_in_samples.len() as f32)
+    }
+
+    pub fn get_minimum(&self) -> Option<f32> {
+        self.latencies_in_samples.iter().copied().reduce(f32::min)
+    }
+
+    pub fn get_maximum(&self) -> Option<f32> {
+        self.latencies_in_samples.iter().copied().reduce(f32::max)
+    }
+
+    pub fn get_jitter(&self) -> Option<f32> {
+        let mean = self.get_mean()?;
+        let variance = self
+            .latencies_in_samples
+            .iter()
+            .map(|latency| (latency - mean).powi(2))
+            .sum::<f32>()
+            / self.latencies_in_samples.len() as f32;
+        Some(variance.sqrt())
+    }
+
+    pub fn get_milliseconds_from_samples(&self, samples: f32) -> f32 {
+        if self.sample_rate <= 0.0 {
+            return 0.0;
+        }
+        samples * 1000.0 / self.sample_rate
+    }
+}
+
+pub struct LatencyRoutine {
+    settings: LatencySettings,
+    settling_blocks_remaining: usize,
+    burst_capture: BurstCapture,
+    result: LatencyResult,
+}
+
+impl LatencyRoutine {
+    pub fn new(settings: LatencySettings) -> Self {
+        Self {
+            settings,
+            settling_blocks_remaining: SETTLING_BLOCKS_BEFORE_FIRST_BURST,
+            burst_capture: BurstCapture::new(),
+            result: LatencyResult::default(),
+        }
+    }
+}
+
+impl MeasurementRoutine for LatencyRoutine {
+    fn begin(&mut self, controller: &MeasurementController) -> Result<(), LocalError> {
+        controller.send_to_ui(EventType::LatencyUpdate(self.result.clone()))?;
+        controller.report_progress(format!(
+            "Measuring burst 1 of {}",
+            self.settings.number_of_bursts
+        ))?;
+        controller.set_tone_waveform(Waveform::Burst)?;
+        controller.set_tone_output_channels(OutputChannels::Both)?;
+        controller.start_tone_and_meter()
+    }
+
+    fn process_burst_played(&mut self, played_at: Instant) {
+        self.burst_capture.set_burst_played_at(played_at);
+    }
+
+    fn process_captured_samples(
+        &mut self,
+        samples: &CapturedSamples,
+        controller: &MeasurementController,
+    ) -> Result<MeasurementStatus, LocalError> {
+        if self.settling_blocks_remaining > 0 {
+            self.settling_blocks_remaining -= 1;
+            if self.settling_blocks_remaining == 0 {
+                controller.trigger_tone_burst()?;
+            }
+            return Ok(MeasurementStatus::Running);
+        }
+
+        if !self.burst_capture.capture(samples)? {
+            return Ok(MeasurementStatus::Running);
+        }
+
+        let arrival = self.burst_capture.get_left_arrival()?;
+        self.result.sample_rate = self.burst_capture.sample_rate();
+        self.result.latencies_in_samples.push(arrival.latency_in_samples);
+        controller.send_to_ui(EventType::LatencyUpdate(self.result.clone()))?;
+
+        self.burst_capture.reset();
+
+        let bursts_measured = self.result.latencies_in_samples.len();
+        if bursts_measured >= self.settings.number_of_bursts {
+            controller.report_progress("Latency complete".to_string())?;
+            return Ok(MeasurementStatus::Complete);
+        }
+
+        controller.report_progress(format!(
+            "Measuring burst {} of {}",
+            bursts_measured + 1,
+            self.settings.number_of_bursts
+        ))?;
+        self.settling_blocks_remaining = SETTLING_BLOCKS_BETWEEN_BURSTS;
+
+        Ok(MeasurementStatus::Running)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn return_latency_statistics_of_measured_bursts() {
+        let result = LatencyResult {
+            sample_rate: 48000.0,
+            latencies_in_samples: vec![432.0, 480.0, 528.0],
+        };
+        assert_eq!(result.get_mean(), Some(480.0));
+        assert_eq!(result.get_minimum(), Some(432.0));
+        assert_eq!(result.get_maximum(), Some(528.0));
+        assert!((result.get_jitter().unwrap() - 39.191_836).abs() < 1e-3);
+        assert_eq!(result.get_milliseconds_from_samples(480.0), 10.0);
+    }
+
+    #[test]
+    fn return_no_latency_statistics_before_any_burst_is_measured() {
+        let result = LatencyResult::default();
+        assert_eq!(result.get_mean(), None);
+        assert_eq!(result.get_jitter(), None);
+    }
+}
@@ -0,0 +1,234 @@
+use crate::analysis::noise::{NoisePower, get_dbfs_from_mean_square, get_noise_power};
+use crate::analysis::{CapturedSamples, get_tone_level_at_frequency};
+use crate::errors::LocalError;
+use crate::events::EventType;
+use crate::measurement::{MeasurementController, MeasurementRoutine, MeasurementStatus};
+use crate::tone_generator::Waveform;
+
+const SETTLING_BLOCKS_PER_PHASE: usize = 2;
+const NOISE_MEASUREMENT_BLOCKS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoiseFloorSettings {
+    pub reference_frequency: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelNoiseFloor {
+    pub noise_floor: f32,
+    pub a_weighted_noise_floor: f32,
+    pub signal_level: Option<f32>,
+}
+
+impl ChannelNoiseFloor {
+    pub fn get_signal_to_noise_ratio(&self) -> Option<f32> {
+        self.signal_level.map(|level| level - self.noise_floor)
+    }
+
+    pub fn get_a_weighted_signal_to_noise_ratio(&self) -> Option<f32> {
+        self.signal_level
+            .map(|level| level - self.a_weighted_noise_floor)
+    }
+
+    pub fn get_dynamic_range(&self) -> f32 {
+        -self.noise_floor
+    }
+
+    pub fn get_a_weighted_dynamic_range(&self) -> f32 {
+        -self.a_weighted_noise_floor
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NoiseFloorResult {
+    pub left: Option<ChannelNoiseFloor>,
+    pub right: Option<ChannelNoiseFloor>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NoiseFloorPhase {
+    Noise,
+    Signal,
+}
+
+pub struct NoiseFloorRoutine {
+    settings: NoiseFloorSettings,
+    phase: NoiseFloorPhase,
+    settling_blocks_remaining: usize,
+    measured_blocks: usize,
+    left_noise_power: NoisePower,
+    right_noise_power: Option<NoisePower>,
+    result: NoiseFloorResult,
+}
+
+impl NoiseFloorRoutine {
+    pub fn new(settings: NoiseFloorSettings) -> Self {
+        Self {
+            settings,
+            phase: NoiseFloorPhase::Noise,
+            settling_blocks_remaining: SETTLING_BLOCKS_PER_PHASE,
+            measured_blocks: 0,
+            left_noise_power: NoisePower::default(),
+            right_noise_power: None,
+            result: NoiseFloorResult::default(),
+        }
+    }
+
+    fn accumulate_noise_power(&mut self, samples: &CapturedSamples) -> bool {
+        let Some(left_noise_power) = get_noise_power(&samples.left, samples.sample_rate) else {
+            return false;
+        };
+        self.left_noise_power.add(&left_noise_power);
+
+        if samples.has_right_channel()
+            && let Some(right_noise_power) = get_noise_power(&samples.right, samples.sample_rate)
+        {
+            self.right_noise_power
+                .get_or_insert_with(NoisePower::default)
+                .add(&right_noise_power);
+        }
+
+        self.measured_blocks += 1;
+        self.measured_blocks >= NOISE_MEASUREMENT_BLOCKS
+    }
+
+    fn finish_noise_phase(&mut self, controller: &MeasurementController) -> Result<(), LocalError> {
+        self.result = NoiseFloorResult {
+            left: Some(get_channel_noise_floor_from_noise_power(
+                &self.left_noise_power.get_average(self.measured_blocks),
+            )),
+            right: self.right_noise_power.map(|noise_power| {
+                get_channel_noise_floor_from_noise_power(&noise_power.get_average(self.measured_blocks))
+            }),
+        };
+        controller.send_to_ui(EventType::NoiseFloorUpdate(self.result))?;
+
+        self.phase = NoiseFloorPhase::Signal;
+        self.settling_blocks_remaining = SETTLING_BLOCKS_PER_PHASE;
+
+        controller.report_progress(format!(
+            "Measuring reference tone at {} Hz",
+            self.settings.reference_frequency
+        ))?;
+        controller.start_tone()
+    }
+
+    fn finish_signal_phase(
+        &mut self,
+        samples: &CapturedSamples,
+        controller: &MeasurementController,
+    ) -> Result<(), LocalError> {
+        let frequency = self.settings.reference_frequency;
+        let left_level = get_tone_level_at_frequency(&samples.left, frequency, samples.sample_rate);
+
+        if !left_level.is_finite() {
+            return Err(LocalError::MeasurementNoSignal(frequency.to_string()));
+        }
+
+        if let Some(left) = self.result.left.as_mut() {
+            left.signal_level = Some(left_level);
+        }
+
+        if let Some(right) = self.result.right.as_mut() {
+            right.signal_level = Some(get_tone_level_at_frequency(
+                &samples.right,
+                frequency,
+                samples.sample_rate,
+            ));
+        }
+
+        controller.send_to_ui(EventType::NoiseFloorUpdate(self.result))?;
+        controller.report_progress("Noise floor complete".to_string())
+    }
+}
+
+impl MeasurementRoutine for NoiseFloorRoutine {
+    fn begin(&mut self, controller: &MeasurementController) -> Result<(), LocalError> {
+        controller.send_to_ui(EventType::NoiseFloorUpdate(self.result))?;
+        controller.report_progress("Measuring noise floor".to_string())?;
+        controller.stop_tone()?;
+        controller.set_tone_waveform(Waveform::Sine)?;
+        controller.set_tone_frequency(self.settings.reference_frequency)?;
+        controller.start_meter()
+    }
+
+    fn process_captured_samples(
+        &mut self,
+        samples: &CapturedSamples,
+        controller: &MeasurementController,
+    ) -> Result<MeasurementStatus, LocalError> {
+        if self.settling_blocks_remaining > 0 {
+            self.settling_blocks_remaining -= 1;
+            return Ok(MeasurementStatus::Running);
+        }
+
+        match self.phase {
+            NoiseFloorPhase::Noise => {
+                if self.accumulate_noise_power(samples) {
+                    self.finish_noise_phase(controller)?;
+                }
+                Ok(MeasurementStatus::Running)
+            }
+            NoiseFloorPhase::Signal => {
+                self.finish_signal_phase(samples, controller)?;
+                Ok(MeasurementStatus::Complete)
+            }
+        }
+    }
+}
+
+fn get_channel_noise_floor_from_noise_power(noise_power: &NoisePower) -> ChannelNoiseFloor {
+    ChannelNoiseFloor {
+        noise_floor: get_dbfs_from_mean_square(noise_power.unweighted),
+        a_weighted_noise_floor: get_dbfs_from_mean_square(noise_power.a_weighted),
+        signal_level: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn return_signal_to_noise_ratio_only_once_the_signal_is_measured() {
+        let mut channel = ChannelNoiseFloor {
+            noise_floor: -100.0,
+            a_weighted_noise_floor: -103.0,
+            signal_level: None,
+        };
+        assert_eq!(channel.get_signal_to_noise_ratio(), None);
+
+        channel.signal_level = Some(-18.0);
+        assert_eq!(channel.get_signal_to_noise_ratio(), Some(82.0));
+        assert_eq!(channel.get_a_weighted_signal_to_noise_ratio(), Some(85.0));
+    }
+
+    #[test]
+    fn return_dynamic_range_relative_to_full_scale() {
+        let channel = get_channel_noise_floor_from_noise_power(&NoisePower {
+            unweighted: 0.5e-10,
+            a_weighted: 0.5e-11,
+        });
+        assert!((channel.get_dynamic_range() - 100.0).abs() < 1e-3);
+        assert!((channel.get_a_weighted_dynamic_range() - 110.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn noise_phase_finishes_after_the_measurement_blocks() {
+        let mut routine = NoiseFloorRoutine::new(NoiseFloorSettings {
+            reference_frequency: 1000.0,
+        });
+        let samples = CapturedSamples {
+            left: vec![0.0; 4096],
+            right: Vec::new(),
+            sample_rate: 48000.0,
+            captured_at: None,
+        };
+
+        for _ in 1..NOISE_MEASUREMENT_BLOCKS {
+            assert!(!routine.accumulate_noise_power(&samples));
+        }
+        assert!(routine.accumulate_noise_power(&samples));
+        assert_eq!(routine.right_noise_power, None);
+    }
+}
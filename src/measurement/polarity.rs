@@ -0,0 +1,135 @@
+use crate::analysis::CapturedSamples;
+use crate::errors::LocalError;
+use crate::events::EventType;
+use crate::measurement::burst_capture::BurstCapture;
+use crate::measurement::{MeasurementController, MeasurementRoutine, MeasurementStatus};
+use crate::tone_generator::{OutputChannels, Waveform};
+use std::time::Instant;
+
+const SETTLING_BLOCKS_BEFORE_BURST: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PolaritySettings {
+    pub reference_frequency: f32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PolarityResult {
+    pub left_inverted: Option<bool>,
+    pub right_inverted: Option<bool>,
+    pub inter_channel_offset_in_samples: Option<f32>,
+    pub sample_rate: f32,
+    pub reference_frequency: f32,
+}
+
+impl PolarityResult {
+    pub fn get_inter_channel_offset_in_microseconds(&self) -> Option<f32> {
+        if self.sample_rate <= 0.0 {
+            return None;
+        }
+        self.inter_channel_offset_in_samples
+            .map(|offset| offset / self.sample_rate * 1_000_000.0)
+    }
+
+    pub fn get_inter_channel_phase_in_degrees(&self) -> Option<f32> {
+        self.get_inter_channel_offset_in_microseconds()
+            .map(|offset| offset / 1_000_000.0 * self.reference_frequency * 360.0)
+    }
+}
+
+pub struct PolarityRoutine {
+    settings: PolaritySettings,
+    settling_blocks_remaining: usize,
+    burst_capture: BurstCapture,
+}
+
+impl PolarityRoutine {
+    pub fn new(settings: PolaritySettings) -> Self {
+        Self {
+            settings,
+            settling_blocks_remaining: SETTLING_BLOCKS_BEFORE_BURST,
+            burst_capture: BurstCapture::new(),
+        }
+    }
+
+    fn get_result_from_burst_capture(&self) -> Result<PolarityResult, LocalError> {
+        let left_arrival = self.burst_capture.get_left_arrival()?;
+        let right_arrival = self.burst_capture.get_right_arrival()?;
+
+        Ok(PolarityResult {
+            left_inverted: Some(left_arrival.is_inverted),
+            right_inverted: right_arrival.map(|arrival| arrival.is_inverted),
+            inter_channel_offset_in_samples: right_arrival
+                .map(|arrival| arrival.interpolated_lag - left_arrival.interpolated_lag),
+            sample_rate: self.burst_capture.sample_rate(),
+            reference_frequency: self.settings.reference_frequency,
+        })
+    }
+}
+
+impl MeasurementRoutine for PolarityRoutine {
+    fn begin(&mut self, controller: &MeasurementController) -> Result<(), LocalError> {
+        controller.send_to_ui(EventType::PolarityUpdate(PolarityResult::default()))?;
+        controller.report_progress("Checking polarity".to_string())?;
+        controller.set_tone_waveform(Waveform::Burst)?;
+        controller.set_tone_output_channels(OutputChannels::Both)?;
+        controller.start_tone_and_meter()
+    }
+
+    fn process_burst_played(&mut self, played_at: Instant) {
+        self.burst_capture.set_burst_played_at(played_at);
+    }
+
+    fn process_captured_samples(
+        &mut self,
+        samples: &CapturedSamples,
+        controller: &MeasurementController,
+    ) -> Result<MeasurementStatus, LocalError> {
+        if self.settling_blocks_remaining > 0 {
+            self.settling_blocks_remaining -= 1;
+            if self.settling_blocks_remaining == 0 {
+                controller.trigger_tone_burst()?;
+            }
+            return Ok(MeasurementStatus::Running);
+        }
+
+        if !self.burst_capture.capture(samples)? {
+            return Ok(MeasurementStatus::Running);
+        }
+
+        controller.send_to_ui(EventType::PolarityUpdate(self.get_result_from_burst_capture()?))?;
+        controller.report_progress("Polarity check complete".to_string())?;
+
+        Ok(MeasurementStatus::Complete)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn return_inter_channel_offset_in_microseconds_and_degrees() {
+        let result = PolarityResult {
+            left_inverted: Some(false),
+            right_inverted: Some(false),
+            inter_channel_offset_in_samples: Some(4.8),
+            sample_rate: 48000.0,
+            reference_frequency: 1000.0,
+        };
+        assert!((result.get_inter_channel_offset_in_microseconds().unwrap() - 100.0).abs() < 1e-3);
+        assert!((result.get_inter_channel_phase_in_degrees().unwrap() - 36.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn return_no_inter_channel_offset_without_a_right_channel() {
+        let result = PolarityResult {
+            left_inverted: Some(true),
+            sample_rate: 48000.0,
+            reference_frequency: 1000.0,
+            ..PolarityResult::default()
+        };
+        assert_eq!(result.get_inter_channel_offset_in_microseconds(), None);
+        assert_eq!(result.get_inter_channel_phase_in_degrees(), None);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::errors::LocalError;
+use crate::measurement::MeasurementController;
+use crate::tone_generator::Waveform;
+
+pub struct SteppedTones {
+    frequencies: Vec<f32>,
+    step_index: usize,
+    settling_blocks: usize,
+    settling_blocks_remaining: usize,
+}
+
+impl SteppedTones {
+    pub fn new(frequencies: Vec<f32>, settling_blocks: usize) -> Self {
+        Self {
+            frequencies,
+            step_index: 0,
+            settling_blocks,
+            settling_blocks_remaining: settling_blocks,
+        }
+    }
+
+    pub fn begin(&mut self, controller: &MeasurementController) -> Result<(), LocalError> {
+        let Some(frequency) = self.frequencies.first() else {
+            return Err(LocalError::MeasurementConfiguration(
+                "No frequencies to measure".to_string(),
+            ));
+        };
+
+        self.step_index = 0;
+        self.settling_blocks_remaining = self.settling_blocks;
+
+        controller.set_tone_waveform(Waveform::Sine)?;
+        controller.set_tone_frequency(*frequency)?;
+        controller.start_tone_and_meter()
+    }
+
+    pub fn current_frequency(&self) -> Option<f32> {
+        self.frequencies.get(self.step_index).copied()
+    }
+
+    pub fn current_step_number(&self) -> usize {
+        self.step_index + 1
+    }
+
+    pub fn number_of_steps(&self) -> usize {
+        self.frequencies.len()
+    }
+
+    pub fn is_settled(&mut self) -> bool {
+        if self.settling_blocks_remaining > 0 {
+            self.settling_blocks_remaining -= 1;
+            return false;
+        }
+        true
+    }
+
+    pub fn advance(&mut self, controller: &MeasurementController) -> Result<bool, LocalError> {
+        self.step_index += 1;
+        self.settling_blocks_remaining = self.settling_blocks;
+
+        match self.current_frequency() {
+            Some(frequency) => {
+                controller.set_tone_frequency(frequency)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+pub fn get_log_spaced_frequencies(
+    start_frequency: f32,
+    stop_frequency: f32,
+    points_per_octave: u32,
+) -> Vec<f32> {
+    if start_frequency <= 0.0 || stop_frequency < start_frequency || points_per_octave == 0 {
+        return Vec::new();
+    }
+
+    let octaves = (stop_frequency / start_frequency).log2();
+    let number_of_points = (octaves * points_per_octave as f32).round() as u32;
+
+    (0..=number_of_points)
+        .map(|point| start_frequency * 2.0_f32.powf(point as f32 / points_per_octave as f32))
+        .filter(|frequency| *frequency <= stop_frequency * 1.001)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn return_octave_spaced_frequencies_for_one_point_per_octave() {
+        let frequencies = get_log_spaced_frequencies(125.0, 1000.0, 1);
+        assert_eq!(frequencies, vec![125.0, 250.0, 500.0, 1000.0]);
+    }
+
+    #[test]
+    fn return_third_octave_spaced_frequencies_within_range() {
+        let frequencies = get_log_spaced_frequencies(20.0, 20000.0, 3);
+        assert_eq!(frequencies.len(), 30);
+        assert_eq!(frequencies[0], 20.0);
+        assert!(*frequencies.last().unwrap() <= 20000.0);
+    }
+
+    #[test]
+    fn return_no_frequencies_for_invalid_range() {
+        assert!(get_log_spaced_frequencies(1000.0, 100.0, 3).is_empty());
+        assert!(get_log_spaced_frequencies(0.0, 100.0, 3).is_empty());
+        assert!(get_log_spaced_frequencies(100.0, 1000.0, 0).is_empty());
+    }
+}
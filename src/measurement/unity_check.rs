@@ -0,0 +1,278 @@
+use crate::analysis::CapturedSamples;
+use crate::errors::LocalError;
+use crate::events::EventType;
+use crate::level_meter::get_peak_value_of_collected_samples;
+use crate::measurement::stepped_tones::SteppedTones;
+use crate::measurement::{MeasurementController, MeasurementRoutine, MeasurementStatus};
+
+const MINIMUM_SETTLING_BLOCKS_PER_STEP: usize = 1;
+const MAXIMUM_SETTLING_BLOCKS_PER_STEP: usize = 10;
+const SETTLED_LEVEL_DIFFERENCE: f32 = 0.02;
+pub const DEFAULT_UNITY_CHECK_FREQUENCIES: [f32; 10] = [
+    31.5, 63.0, 125.0, 250.0, 500.0, 1000.0, 2000.0, 4000.0, 8000.0, 16000.0,
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnityCheckSettings {
+    pub frequencies: Vec<f32>,
+    pub reference_level: f32,
+    pub tolerance: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnityCheckStep {
+    pub frequency: f32,
+    pub left_delta: f32,
+    pub right_delta: Option<f32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UnityCheckResult {
+    pub tolerance: f32,
+    pub steps: Vec<UnityCheckStep>,
+}
+
+pub struct UnityCheckRoutine {
+    settings: UnityCheckSettings,
+    stepped_tones: SteppedTones,
+    previous_levels: Option<(f32, Option<f32>)>,
+    settling_blocks_waited: usize,
+    result: UnityCheckResult,
+}
+
+impl UnityCheckRoutine {
+    pub fn new(settings: UnityCheckSettings) -> Self {
+        let stepped_tones =
+            SteppedTones::new(settings.frequencies.clone(), MINIMUM_SETTLING_BLOCKS_PER_STEP);
+        let result = UnityCheckResult {
+            tolerance: settings.tolerance,
+            steps: Vec::new(),
+        };
+
+        Self {
+            settings,
+            stepped_tones,
+            previous_levels: None,
+            settling_blocks_waited: 0,
+            result,
+        }
+    }
+
+    fn is_level_settled(&mut self, levels: (f32, Option<f32>)) -> bool {
+        self.settling_blocks_waited += 1;
+
+        let settled = match self.previous_levels {
+            Some((previous_left, previous_right)) => {
+                is_level_difference_settled(previous_left, levels.0)
+                    && previous_right
+                        .zip(levels.1)
+                        .is_none_or(|(previous, current)| {
+                            is_level_difference_settled(previous, current)
+                        })
+            }
+            None => false,
+        };
+
+        self.previous_levels = Some(levels);
+
+        settled || self.settling_blocks_waited >= MAXIMUM_SETTLING_BLOCKS_PER_STEP
+    }
+}
+
+impl MeasurementRoutine for UnityCheckRoutine {
+    fn begin(&mut self, controller: &MeasurementController) -> Result<(), LocalError> {
+        controller.send_to_ui(EventType::UnityCheckUpdate(self.result.clone()))?;
+        controller.report_progress(format!(
+            "Checking step 1 of {}",
+            self.stepped_tones.number_of_steps()
+        ))?;
+        self.stepped_tones.begin(controller)
+    }
+
+    fn process_captured_samples(
+        &mut self,
+        samples: &CapturedSamples,
+        controller: &MeasurementController,
+    ) -> Result<MeasurementStatus, LocalError> {
+        if !self.stepped_tones.is_settled() {
+            return Ok(MeasurementStatus::Running);
+        }
+
+        let Some(frequency) = self.stepped_tones.current_frequency() else {
+            return Ok(MeasurementStatus::Complete);
+        };
+
+        let left_level = get_peak_value_of_collected_samples(&samples.left);
+        let right_level = samples
+            .has_right_channel()
+            .then(|| get_peak_value_of_collected_samples(&samples.right));
+
+        if !self.is_level_settled((left_level, right_level)) {
+            return Ok(MeasurementStatus::Running);
+        }
+
+        self.result.steps.push(UnityCheckStep {
+            frequency,
+            left_delta: left_level - self.settings.reference_level,
+            right_delta: right_level.map(|level| level - self.settings.reference_level),
+        });
+        controller.send_to_ui(EventType::UnityCheckUpdate(self.result.clone()))?;
+
+        self.previous_levels = None;
+        self.settling_blocks_waited = 0;
+
+        if !self.stepped_tones.advance(controller)? {
+            controller.report_progress("Unity check complete".to_string())?;
+            return Ok(MeasurementStatus::Complete);
+        }
+
+        controller.report_progress(format!(
+            "Checking step {} of {}",
+            self.stepped_tones.current_step_number(),
+            self.stepped_tones.number_of_steps()
+        ))?;
+
+        Ok(MeasurementStatus::Running)
+    }
+}
+
+pub fn get_frequencies_from_text(frequency_list: &str) -> Result<Vec<f32>, LocalError> {
+    let frequencies = frequency_list
+        .split([',', ' ', ';'])
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            entry
+                .trim()
+                .parse::<f32>()
+                .ok()
+                .filter(|frequency| *frequency > 0.0)
+                .ok_or_else(|| LocalError::MeasurementConfiguration(format!(
+                    "{} is not a valid frequency",
+                    entry.trim()
+                )))
+        })
+        .collect::<Result<Vec<f32>, LocalError>>()?;
+
+    if frequencies.is_empty() {
+        return Err(LocalError::MeasurementConfiguration(
+            "No frequencies to measure".to_string(),
+        ));
+    }
+
+    Ok(frequencies)
+}
+
+pub fn get_tolerance_from_text(tolerance: &str) -> Result<f32, LocalError> {
+    tolerance
+        .trim()
+        .parse::<f32>()
+        .ok()
+        .filter(|tolerance| tolerance.is_finite() && *tolerance > 0.0)
+        .ok_or_else(|| {
+            LocalError::MeasurementConfiguration(format!(
+                "{} is not a valid tolerance",
+                tolerance.trim()
+            ))
+        })
+}
+
+pub fn is_delta_within_tolerance(delta: f32, tolerance: f32) -> bool {
+    delta.is_finite() && delta.abs() <= tolerance
+}
+
+fn is_level_difference_settled(previous: f32, current: f32) -> bool {
+    (previous - current).abs() < SETTLED_LEVEL_DIFFERENCE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::dc_offset::DcBlockingFilter;
+    use crate::analysis::generate_sine_samples;
+    use crate::level_meter::get_dc_blocked_samples;
+    use crossbeam_channel::unbounded;
+
+    #[test]
+    fn return_frequencies_from_comma_separated_text() {
+        let frequencies = get_frequencies_from_text("31.5, 63,125 1000").unwrap();
+        assert_eq!(frequencies, vec![31.5, 63.0, 125.0, 1000.0]);
+    }
+
+    #[test]
+    fn return_error_from_text_with_invalid_frequency() {
+        let result = get_frequencies_from_text("100, abc");
+        let expected_error =
+            LocalError::MeasurementConfiguration("abc is not a valid frequency".to_string());
+        assert_eq!(result, Err(expected_error));
+    }
+
+    #[test]
+    fn return_error_from_empty_frequency_text() {
+        assert!(get_frequencies_from_text(" , ").is_err());
+    }
+
+    #[test]
+    fn return_tolerance_only_when_it_is_a_positive_number() {
+        assert_eq!(get_tolerance_from_text(" 0.25 "), Ok(0.25));
+        let expected_error =
+            LocalError::MeasurementConfiguration("abc is not a valid tolerance".to_string());
+        assert_eq!(get_tolerance_from_text("abc"), Err(expected_error));
+        assert!(get_tolerance_from_text("0").is_err());
+        assert!(get_tolerance_from_text("-0.1").is_err());
+    }
+
+    #[test]
+    fn delta_is_within_tolerance_only_when_finite_and_inside_the_window() {
+        assert!(is_delta_within_tolerance(-0.1, 0.1));
+        assert!(!is_delta_within_tolerance(0.11, 0.1));
+        assert!(!is_delta_within_tolerance(f32::NEG_INFINITY, 0.1));
+    }
+
+    #[test]
+    fn level_is_settled_once_consecutive_blocks_match() {
+        let mut routine = UnityCheckRoutine::new(UnityCheckSettings {
+            frequencies: vec![1000.0],
+            reference_level: -18.0,
+            tolerance: 0.1,
+        });
+        assert!(!routine.is_level_settled((-18.5, None)));
+        assert!(!routine.is_level_settled((-18.1, None)));
+        assert!(routine.is_level_settled((-18.09, None)));
+    }
+
+    #[test]
+    fn step_on_an_offset_sine_matches_the_dc_blocked_live_peak() {
+        let (sender, _receiver) = unbounded();
+        let controller = MeasurementController {
+            tone_generator_sender: sender.clone(),
+            level_meter_sender: sender.clone(),
+            user_interface_sender: sender,
+        };
+        let offset_sine: Vec<f32> = generate_sine_samples(1000.0, 0.5, 0.0, 48000.0, 9600)
+            .iter()
+            .map(|sample| 0.1 + sample)
+            .collect();
+        let mut filter = DcBlockingFilter::default();
+        get_dc_blocked_samples(&offset_sine, &mut filter, 48000.0, true);
+        let blocked_samples = get_dc_blocked_samples(&offset_sine, &mut filter, 48000.0, true).unwrap();
+        let live_peak = get_peak_value_of_collected_samples(&blocked_samples);
+        let captured = CapturedSamples {
+            left: blocked_samples,
+            sample_rate: 48000.0,
+            ..CapturedSamples::default()
+        };
+        let mut routine = UnityCheckRoutine::new(UnityCheckSettings {
+            frequencies: vec![1000.0],
+            reference_level: -6.0,
+            tolerance: 0.1,
+        });
+
+        routine.begin(&controller).unwrap();
+        while routine.result.steps.is_empty() {
+            routine.process_captured_samples(&captured, &controller).unwrap();
+        }
+
+        assert!((routine.result.steps[0].left_delta - (live_peak + 6.0)).abs() < 1e-5);
+        assert!((live_peak - 20.0 * 0.5_f32.log10()).abs() < 0.01);
+    }
+}
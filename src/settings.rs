@@ -0,0 +1,372 @@
+use crate::calibration::{AnalogUnit, Calibration, CalibrationDirection, DeviceCalibrations};
+use crate::device_manager::CurrentDevice;
+use crate::errors::LocalError;
+use crate::tone_generator::Waveform;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+pub mod preset;
+
+pub const DEFAULT_REFERENCE_FREQUENCY: f32 = 1000.0;
+pub const DEFAULT_REFERENCE_LEVEL: f32 = -18.0;
+pub const DEFAULT_DELTA_MODE: bool = true;
+pub const DEFAULT_UNITY_TOLERANCE: f32 = 0.1;
+const MINIMUM_REFERENCE_LEVEL: f32 = -60.0;
+const MAXIMUM_REFERENCE_LEVEL: f32 = 0.0;
+const SETTINGS_DIRECTORY_NAME: &str = "stereo-unity-gain";
+const SETTINGS_FILE_NAME: &str = "settings.toml";
+const SETTINGS_KEYS: [&str; 13] = [
+    "reference_frequency",
+    "reference_level",
+    "waveform",
+    "delta_mode_active",
+    "unity_tolerance",
+    "close_tolerance",
+    "analog_unit",
+    "meter_display",
+    "colour_theme",
+    "input_device",
+    "output_device",
+    "window",
+    "calibration",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MeterDisplay {
+    #[default]
+    Numbers,
+    Bars,
+    Both,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColourTheme {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+    BlueOrange,
+    TealPink,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_device: Option<CurrentDevice>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_device: Option<CurrentDevice>,
+    pub reference_frequency: f32,
+    pub reference_level: f32,
+    pub waveform: Waveform,
+    pub delta_mode_active: bool,
+    pub unity_tolerance: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close_tolerance: Option<f32>,
+    pub analog_unit: AnalogUnit,
+    pub meter_display: MeterDisplay,
+    pub colour_theme: ColourTheme,
+    #[serde(
+        rename = "calibration",
+        skip_serializing_if = "DeviceCalibrations::is_empty",
+        serialize_with = "serialize_calibrations",
+        deserialize_with = "deserialize_calibrations"
+    )]
+    pub calibrations: DeviceCalibrations,
+    #[serde(rename = "window", skip_serializing_if = "Option::is_none")]
+    pub window_geometry: Option<WindowGeometry>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            input_device: None,
+            output_device: None,
+            reference_frequency: DEFAULT_REFERENCE_FREQUENCY,
+            reference_level: DEFAULT_REFERENCE_LEVEL,
+            waveform: Waveform::Sine,
+            delta_mode_active: DEFAULT_DELTA_MODE,
+            unity_tolerance: DEFAULT_UNITY_TOLERANCE,
+            close_tolerance: None,
+            analog_unit: AnalogUnit::Dbfs,
+            meter_display: MeterDisplay::Numbers,
+            colour_theme: ColourTheme::Dark,
+            calibrations: DeviceCalibrations::default(),
+            window_geometry: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CalibrationEntry {
+    direction: CalibrationDirection,
+    device: String,
+    dbfs: f32,
+    dbu: f32,
+}
+
+pub fn get_settings_file_path() -> Option<PathBuf> {
+    let config_directory = if cfg!(target_os = "windows") {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join("Library").join("Application Support"))
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .filter(|directory| !directory.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    }?;
+
+    Some(
+        config_directory
+            .join(SETTINGS_DIRECTORY_NAME)
+            .join(SETTINGS_FILE_NAME),
+    )
+}
+
+pub fn read_settings_file(file_path: &Path) -> Result<Settings, LocalError> {
+    match fs::read_to_string(file_path) {
+        Ok(text) => {
+            get_settings_from_text(&text).map_err(|err| LocalError::SettingsRead(err.to_string()))
+        }
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(Settings::default()),
+        Err(err) => Err(LocalError::SettingsRead(err.to_string())),
+    }
+}
+
+pub fn write_settings_file(file_path: &Path, settings: &Settings) -> Result<(), LocalError> {
+    if let Some(directory) = file_path.parent() {
+        fs::create_dir_all(directory).map_err(|err| LocalError::SettingsWrite(err.to_string()))?;
+    }
+
+    let text = get_text_from_settings(settings).map_err(|err| LocalError::SettingsWrite(err.to_string()))?;
+
+    fs::write(file_path, text).map_err(|err| LocalError::SettingsWrite(err.to_string()))
+}
+
+fn get_text_from_settings(settings: &Settings) -> Result<String, toml::ser::Error> {
+    toml::to_string(settings)
+}
+
+fn get_settings_from_text(text: &str) -> Result<Settings, toml::de::Error> {
+    toml::from_str(text).map(get_validated_settings)
+}
+
+fn get_validated_settings(settings: Settings) -> Settings {
+    let defaults = Settings::default();
+
+    Settings {
+        reference_frequency: Some(settings.reference_frequency)
+            .filter(|frequency| frequency.is_finite() && *frequency > 0.0)
+            .unwrap_or(defaults.reference_frequency),
+        reference_level: Some(settings.reference_level)
+            .filter(|level| level.is_finite())
+            .map(|level| level.clamp(MINIMUM_REFERENCE_LEVEL, MAXIMUM_REFERENCE_LEVEL))
+            .unwrap_or(defaults.reference_level),
+        unity_tolerance: Some(settings.unity_tolerance)
+            .filter(|tolerance| tolerance.is_finite() && *tolerance > 0.0)
+            .unwrap_or(defaults.unity_tolerance),
+        close_tolerance: settings
+            .close_tolerance
+            .filter(|tolerance| tolerance.is_finite() && *tolerance > 0.0),
+        window_geometry: settings
+            .window_geometry
+            .filter(|geometry| geometry.width > 0 && geometry.height > 0),
+        ..settings
+    }
+}
+
+fn has_settings_values(text: &str) -> bool {
+    toml::from_str::<toml::Table>(text)
+        .is_ok_and(|table| table.keys().any(|key| SETTINGS_KEYS.contains(&key.as_str())))
+}
+
+fn serialize_calibrations<S: Serializer>(
+    calibrations: &DeviceCalibrations,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let entries: BTreeMap<String, CalibrationEntry> = calibrations
+        .iter()
+        .enumerate()
+        .map(|(index, (direction, device_name, calibration))| {
+            (
+                (index + 1).to_string(),
+                CalibrationEntry {
+                    direction,
+                    device: device_name.clone(),
+                    dbfs: calibration.dbfs,
+                    dbu: calibration.dbu,
+                },
+            )
+        })
+        .collect();
+
+    entries.serialize(serializer)
+}
+
+fn deserialize_calibrations<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<DeviceCalibrations, D::Error> {
+    let entries = BTreeMap::<String, toml::Value>::deserialize(deserializer)?;
+    let mut calibrations = DeviceCalibrations::default();
+
+    for entry in entries
+        .into_values()
+        .filter_map(|entry| entry.try_into::<CalibrationEntry>().ok())
+        .filter(|entry| entry.dbfs.is_finite() && entry.dbu.is_finite())
+    {
+        calibrations.set(
+            entry.direction,
+            &entry.device,
+            Some(Calibration {
+                dbfs: entry.dbfs,
+                dbu: entry.dbu,
+            }),
+        );
+    }
+
+    Ok(calibrations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_settings() -> Settings {
+        Settings {
+            input_device: Some(CurrentDevice {
+                name: "Studio \"A\" Interface\\In".to_string(),
+                left_channel: "3".to_string(),
+                right_channel: Some("4".to_string()),
+            }),
+            output_device: Some(CurrentDevice {
+                name: "Headphones".to_string(),
+                left_channel: "1".to_string(),
+                right_channel: None,
+            }),
+            reference_frequency: 997.5,
+            reference_level: -20.5,
+            waveform: Waveform::Square,
+            delta_mode_active: false,
+            unity_tolerance: 0.05,
+            close_tolerance: Some(0.5),
+            analog_unit: AnalogUnit::Dbu,
+            meter_display: MeterDisplay::Both,
+            colour_theme: ColourTheme::BlueOrange,
+            calibrations: get_test_calibrations(),
+            window_geometry: Some(WindowGeometry {
+                x: -40,
+                y: 25,
+                width: 1280,
+                height: 960,
+            }),
+        }
+    }
+
+    fn get_test_calibrations() -> DeviceCalibrations {
+        let mut calibrations = DeviceCalibrations::default();
+        calibrations.set(
+            CalibrationDirection::Input,
+            "Studio \"A\" Interface\\In",
+            Some(Calibration {
+                dbfs: 0.0,
+                dbu: 24.0,
+            }),
+        );
+        calibrations.set(
+            CalibrationDirection::Output,
+            "Headphones",
+            Some(Calibration {
+                dbfs: -18.0,
+                dbu: 4.0,
+            }),
+        );
+        calibrations
+    }
+
+    #[test]
+    fn settings_round_trip_through_the_settings_text() {
+        let settings = get_test_settings();
+        assert_eq!(
+            get_settings_from_text(&get_text_from_settings(&settings).unwrap()).unwrap(),
+            settings
+        );
+        assert_eq!(
+            get_settings_from_text(&get_text_from_settings(&Settings::default()).unwrap()).unwrap(),
+            Settings::default()
+        );
+    }
+
+    #[test]
+    fn invalid_or_missing_values_fall_back_to_the_defaults() {
+        let text = "reference_frequency = -5\nunity_tolerance = 0.0\nclose_tolerance = -1.0\n\
+                    [window]\nx = 10\ny = 0\nwidth = 0\nheight = 900\n";
+        assert_eq!(get_settings_from_text(text).unwrap(), Settings::default());
+    }
+
+    #[test]
+    fn return_an_error_for_malformed_settings_text() {
+        assert!(get_settings_from_text("reference_level = loud").is_err());
+        assert!(get_settings_from_text("waveform = \"noise\"").is_err());
+        assert!(get_settings_from_text("[input_device]\nleft_channel = \"1\"").is_err());
+    }
+
+    #[test]
+    fn clamp_saved_reference_level_to_the_supported_range() {
+        let get_reference_level = |text| get_settings_from_text(text).unwrap().reference_level;
+        assert_eq!(get_reference_level("reference_level = -90.0"), -60.0);
+        assert_eq!(get_reference_level("reference_level = 3"), 0.0);
+        assert_eq!(get_reference_level("reference_level = -18.5"), -18.5);
+    }
+
+    #[test]
+    fn ignore_comments_blank_lines_and_unknown_keys() {
+        let text = "# saved settings\n\nreference_level = -12 # dBFS\ncolour = \"red\"\n";
+        let settings = get_settings_from_text(text).unwrap();
+        assert_eq!(settings.reference_level, -12.0);
+        assert_eq!(settings.reference_frequency, DEFAULT_REFERENCE_FREQUENCY);
+    }
+
+    #[test]
+    fn skip_calibrations_with_a_missing_or_invalid_value() {
+        let text = "[calibration.1]\ndirection = \"input\"\ndevice = \"A\"\ndbfs = 0\ndbu = 24\n\
+                    [calibration.2]\ndirection = \"sideways\"\ndevice = \"B\"\ndbfs = 0\ndbu = 24\n\
+                    [calibration.3]\ndirection = \"output\"\ndevice = \"C\"\ndbfs = -18\n";
+        let calibrations = get_settings_from_text(text).unwrap().calibrations;
+        assert_eq!(
+            calibrations.get(CalibrationDirection::Input, "A"),
+            Some(Calibration {
+                dbfs: 0.0,
+                dbu: 24.0
+            })
+        );
+        assert_eq!(calibrations.iter().count(), 1);
+    }
+
+    #[test]
+    fn burst_waveform_is_saved_as_sine() {
+        let settings = Settings {
+            waveform: Waveform::Burst,
+            ..Settings::default()
+        };
+        assert_eq!(
+            get_settings_from_text(&get_text_from_settings(&settings).unwrap()).unwrap().waveform,
+            Waveform::Sine
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use super::{ColourTheme, DeviceCalibrations, Settings, get_settings_file_path, get_settings_from_text, get_text_from_settings, has_settings_values};
+use crate::errors::LocalError;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const PRESETS_DIRECTORY_NAME: &str = "presets";
+const PRESET_FILE_EXTENSION: &str = "toml";
+const PRESET_COMMAND_LINE_FLAG: &str = "--preset";
+
+pub fn get_presets_directory() -> Option<PathBuf> {
+    Some(
+        get_settings_file_path()?
+            .parent()?
+            .join(PRESETS_DIRECTORY_NAME),
+    )
+}
+
+pub fn get_preset_names(directory: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == PRESET_FILE_EXTENSION)
+        })
+        .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_string()))
+        .collect();
+
+    names.sort_by_key(|name| name.to_lowercase());
+    names
+}
+
+pub fn get_preset_file_path(directory: &Path, name: &str) -> Result<PathBuf, LocalError> {
+    let file_name: String = name
+        .trim()
+        .chars()
+        .map(|character| {
+            if character.is_alphanumeric() || matches!(character, ' ' | '-' | '_' | '.') {
+                character
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    if file_name.trim_matches('.').is_empty() {
+        return Err(LocalError::PresetName(name.to_string()));
+    }
+
+    Ok(directory.join(format!("{file_name}.{PRESET_FILE_EXTENSION}")))
+}
+
+pub fn read_preset_file(file_path: &Path) -> Result<Settings, LocalError> {
+    let text = fs::read_to_string(file_path)
+        .map_err(|err| LocalError::PresetRead(format!("{}: {err}", file_path.display())))?;
+
+    if !has_settings_values(&text) {
+        return Err(LocalError::PresetRead(format!(
+            "{}: the file contains no preset settings",
+            file_path.display()
+        )));
+    }
+
+    get_settings_from_text(&text)
+        .map_err(|err| LocalError::PresetRead(format!("{}: {err}", file_path.display())))
+}
+
+pub fn write_preset_file(file_path: &Path, preset: &Settings) -> Result<(), LocalError> {
+    if let Some(directory) = file_path.parent() {
+        fs::create_dir_all(directory).map_err(|err| LocalError::PresetWrite(err.to_string()))?;
+    }
+
+    let preset = Settings {
+        window_geometry: None,
+        calibrations: DeviceCalibrations::default(),
+        colour_theme: ColourTheme::default(),
+        ..preset.clone()
+    };
+
+    let text = get_text_from_settings(&preset).map_err(|err| LocalError::PresetWrite(err.to_string()))?;
+
+    fs::write(file_path, text).map_err(|err| LocalError::PresetWrite(err.to_string()))
+}
+
+pub fn apply_preset(settings: &mut Settings, preset: &Settings) {
+    *settings = Settings {
+        window_geometry: settings.window_geometry,
+        calibrations: settings.calibrations.clone(),
+        colour_theme: settings.colour_theme,
+        input_device: preset
+            .input_device
+            .clone()
+            .or_else(|| settings.input_device.clone()),
+        output_device: preset
+            .output_device
+            .clone()
+            .or_else(|| settings.output_device.clone()),
+        ..preset.clone()
+    };
+}
+
+pub fn get_preset_argument<I: Iterator<Item = String>>(mut arguments: I) -> Option<String> {
+    while let Some(argument) = arguments.next() {
+        if argument == PRESET_COMMAND_LINE_FLAG {
+            return arguments.next();
+        }
+
+        if let Some(value) = argument
+            .strip_prefix(PRESET_COMMAND_LINE_FLAG)
+            .and_then(|value| value.strip_prefix('='))
+        {
+            return Some(value.to_string());
+        }
+    }
+
+    None
+}
+
+pub fn get_preset_file_path_from_argument(
+    directory: Option<&Path>,
+    argument: &str,
+) -> Result<PathBuf, LocalError> {
+    let file_path = PathBuf::from(argument);
+    if file_path.is_file() {
+        return Ok(file_path);
+    }
+
+    let directory = directory.ok_or(LocalError::PresetDirectory)?;
+    get_preset_file_path(directory, argument)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calibration::{Calibration, CalibrationDirection};
+    use crate::device_manager::CurrentDevice;
+    use crate::settings::WindowGeometry;
+
+    #[test]
+    fn return_preset_file_path_with_unsafe_characters_replaced() {
+        let directory = Path::new("presets");
+        assert_eq!(
+            get_preset_file_path(directory, " Room A / -20 ").unwrap(),
+            directory.join("Room A _ -20.toml")
+        );
+        assert_eq!(
+            get_preset_file_path(directory, " .. "),
+            Err(LocalError::PresetName(" .. ".to_string()))
+        );
+    }
+
+    #[test]
+    fn reject_an_imported_file_that_is_not_a_preset() {
+        let directory = std::env::temp_dir().join(format!("stereo-unity-gain-preset-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let notes_path = directory.join("notes.txt");
+        let preset_path = directory.join("Room A.toml");
+        fs::write(&notes_path, "shopping list\nmilk = 2\n").unwrap();
+        fs::write(&preset_path, "reference_level = -20.0\n").unwrap();
+
+        let notes = read_preset_file(&notes_path);
+        let preset = read_preset_file(&preset_path);
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert!(matches!(notes, Err(LocalError::PresetRead(_))));
+        assert_eq!(preset.unwrap().reference_level, -20.0);
+    }
+
+    #[test]
+    fn return_preset_name_from_either_form_of_the_command_line_flag() {
+        let arguments = |list: &[&str]| {
+            list.iter()
+                .map(|argument| argument.to_string())
+                .collect::<Vec<String>>()
+                .into_iter()
+        };
+        assert_eq!(
+            get_preset_argument(arguments(&["--preset", "Room B"])),
+            Some("Room B".to_string())
+        );
+        assert_eq!(
+            get_preset_argument(arguments(&["-v", "--preset=Room C"])),
+            Some("Room C".to_string())
+        );
+        assert_eq!(get_preset_argument(arguments(&["--preset"])), None);
+        assert_eq!(get_preset_argument(arguments(&[])), None);
+    }
+
+    #[test]
+    fn applying_a_preset_keeps_the_window_calibrations_theme_and_any_device_the_preset_does_not_name() {
+        let saved_device = CurrentDevice {
+            name: "Interface".to_string(),
+            left_channel: "1".to_string(),
+            right_channel: Some("2".to_string()),
+        };
+        let geometry = WindowGeometry {
+            x: 0,
+            y: 0,
+            width: 1000,
+            height: 920,
+        };
+        let mut calibrations = DeviceCalibrations::default();
+        calibrations.set(
+            CalibrationDirection::Input,
+            "Interface",
+            Some(Calibration {
+                dbfs: -18.0,
+                dbu: 4.0,
+            }),
+        );
+        let mut settings = Settings {
+            input_device: Some(saved_device.clone()),
+            calibrations: calibrations.clone(),
+            window_geometry: Some(geometry),
+            colour_theme: ColourTheme::HighContrast,
+            ..Settings::default()
+        };
+        let preset = Settings {
+            reference_frequency: 400.0,
+            reference_level: -20.0,
+            ..Settings::default()
+        };
+
+        apply_preset(&mut settings, &preset);
+
+        assert_eq!(settings.reference_frequency, 400.0);
+        assert_eq!(settings.reference_level, -20.0);
+        assert_eq!(settings.input_device, Some(saved_device));
+        assert_eq!(settings.window_geometry, Some(geometry));
+        assert_eq!(settings.calibrations, calibrations);
+        assert_eq!(settings.colour_theme, ColourTheme::HighContrast);
+    }
+}
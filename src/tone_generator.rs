@@ -2,30 +2,88 @@ use crate::device_manager::{CurrentDevice, get_channel_indexes_from_channel_name
 use crate::errors::{EXIT_CODE_ERROR, LocalError};
 use crate::events::EventType;
 use cpal::traits::{StreamTrait, DeviceTrait, HostTrait};
-use cpal::{Device, Stream, default_host};
+use cpal::{Device, OutputStreamTimestamp, Stream, default_host};
+use burst::Burst;
 use crossbeam_channel::{Receiver, Sender};
+use guide::{Guide, get_guide_beep};
+use rtrb::{Consumer, Producer, RingBuffer};
+use serde::{Deserialize, Serialize};
 use sine::Sine;
 use square::Square;
+use sweep::{SharedSweepFrequency, Sweep, SweepSettings};
 use std::error::Error;
 use std::process::exit;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
+pub mod burst;
+pub mod guide;
 mod sine;
 mod square;
+pub mod sweep;
 
 const ERROR_MESSAGE_OUTPUT_STREAM_ERROR: &str = "Output Stream Error!";
 const MINIMUM_DBFS_FACTOR_THRESHOLD: f32 = 0.001;
+const BURST_PLAYED_RING_BUFFER_SIZE: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Waveform {
+    #[default]
+    Sine,
+    Square,
+    Sweep,
+    #[serde(rename(serialize = "sine"), skip_deserializing)]
+    Burst,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum OutputChannels {
+    #[default]
+    Both,
+    LeftOnly,
+    RightOnly,
+}
+
+impl OutputChannels {
+    fn is_left_enabled(self) -> bool {
+        self != OutputChannels::RightOnly
+    }
+
+    fn is_right_enabled(self) -> bool {
+        self != OutputChannels::LeftOnly
+    }
+}
 
 pub trait WaveShape {
     fn new(sample_rate: f32) -> Self;
     fn generate_tone_sample(&mut self, _reference_frequency: f32, target_level: f32) -> f32;
 }
 
-pub struct ToneGenerator {
-    output_stream: Option<Stream>,
-    sine_mode_enabled: Arc<Mutex<bool>>,
+#[derive(Clone)]
+struct SharedToneState {
+    waveform: Arc<Mutex<Waveform>>,
+    output_channels: Arc<Mutex<OutputChannels>>,
     reference_frequency: Arc<Mutex<f32>>,
     reference_level: Arc<Mutex<f32>>,
+    sweep_settings: Arc<Mutex<SweepSettings>>,
+    sweep_restart_requested: Arc<Mutex<bool>>,
+    burst_requested: Arc<Mutex<bool>>,
+    burst_played_producer: Arc<Mutex<Producer<Instant>>>,
+    sweep_frequency: SharedSweepFrequency,
+}
+
+#[derive(Clone, Default)]
+struct SharedGuideState {
+    delta: Arc<Mutex<Option<f32>>>,
+    unity_tolerance: Arc<Mutex<f32>>,
+}
+
+pub struct ToneGenerator {
+    output_stream: Option<Stream>,
+    guide_stream: Option<Stream>,
+    shared_state: SharedToneState,
+    shared_guide_state: SharedGuideState,
     ui_command_receiver: Receiver<EventType>,
     user_interface_sender: Sender<EventType>,
 }
@@ -36,16 +94,26 @@ impl ToneGenerator {
         reference_level: f32,
         ui_command_receiver: Receiver<EventType>,
         user_interface_sender: Sender<EventType>,
+        burst_played_producer: Producer<Instant>,
+        sweep_frequency: SharedSweepFrequency,
     ) -> Result<Self, Box<dyn Error>> {
-        let reference_frequency_arc = Arc::new(Mutex::new(reference_frequency));
-        let reference_level_arc = Arc::new(Mutex::new(reference_level));
-        let sine_mode_arc = Arc::new(Mutex::new(true));
+        let shared_state = SharedToneState {
+            waveform: Arc::new(Mutex::new(Waveform::default())),
+            output_channels: Arc::new(Mutex::new(OutputChannels::default())),
+            reference_frequency: Arc::new(Mutex::new(reference_frequency)),
+            reference_level: Arc::new(Mutex::new(reference_level)),
+            sweep_settings: Arc::new(Mutex::new(SweepSettings::default())),
+            sweep_restart_requested: Arc::new(Mutex::new(false)),
+            burst_requested: Arc::new(Mutex::new(false)),
+            burst_played_producer: Arc::new(Mutex::new(burst_played_producer)),
+            sweep_frequency,
+        };
 
         Ok(Self {
-            sine_mode_enabled: sine_mode_arc,
-            reference_frequency: reference_frequency_arc,
-            reference_level: reference_level_arc,
+            shared_state,
+            shared_guide_state: SharedGuideState::default(),
             output_stream: None,
+            guide_stream: None,
             ui_command_receiver,
             user_interface_sender,
         })
@@ -57,25 +125,63 @@ impl ToneGenerator {
             if let Ok(event) = ui_command_receiver.try_recv() {
                 match event {
                     EventType::Start => self.start().expect("Could Not Start Tone Generator"),
-                    EventType::Stop => self.stop().expect("Could Not Stop Tone Generator"),
+                    EventType::Stop => {
+                        self.stop().expect("Could Not Stop Tone Generator");
+                        self.silence_guide();
+                    }
                     EventType::ToneFrequencyUpdate(new_frequency) => {
-                        if let Ok(mut freq) = self.reference_frequency.lock() {
+                        if let Ok(mut freq) = self.shared_state.reference_frequency.lock() {
                             *freq = new_frequency;
                         }
                     }
                     EventType::ToneLevelUpdate(new_level) => {
-                        if let Ok(mut level) = self.reference_level.lock() {
+                        if let Ok(mut level) = self.shared_state.reference_level.lock() {
                             *level = new_level;
                         }
                     }
-                    EventType::ToneModeUpdate(sine_enabled) => {
-                        if let Ok(mut sine_mode_enabled) = self.sine_mode_enabled.lock() {
-                            *sine_mode_enabled = sine_enabled;
+                    EventType::ToneWaveformUpdate(new_waveform) => {
+                        if let Ok(mut waveform) = self.shared_state.waveform.lock() {
+                            *waveform = new_waveform;
+                        }
+                        self.request_sweep_restart();
+                    }
+                    EventType::ToneOutputChannelsUpdate(new_output_channels) => {
+                        if let Ok(mut output_channels) = self.shared_state.output_channels.lock() {
+                            *output_channels = new_output_channels;
+                        }
+                    }
+                    EventType::ToneBurstTrigger => {
+                        if let Ok(mut burst_requested) = self.shared_state.burst_requested.lock() {
+                            *burst_requested = true;
+                        }
+                    }
+                    EventType::ToneSweepUpdate(new_settings) => {
+                        if let Ok(mut settings) = self.shared_state.sweep_settings.lock() {
+                            *settings = new_settings;
                         }
+                        self.request_sweep_restart();
                     }
                     EventType::ToneDeviceUpdate { name, left, right } => {
                         self.update_output_stream_on_new_device(&name, &left, right.as_ref())?;
                     }
+                    EventType::GuideLevelUpdate {
+                        delta,
+                        unity_tolerance,
+                    } => {
+                        if let Ok(mut guide_delta) = self.shared_guide_state.delta.lock() {
+                            *guide_delta = delta;
+                        }
+                        if let Ok(mut tolerance) = self.shared_guide_state.unity_tolerance.lock() {
+                            *tolerance = unity_tolerance;
+                        }
+                    }
+                    EventType::GuideDeviceUpdate(name) => {
+                        if let Err(error) = self.update_guide_stream_on_new_device(name.as_deref()) {
+                            let _ = self
+                                .user_interface_sender
+                                .send(EventType::RecoverableError(error.to_string()));
+                        }
+                    }
                     _ => (),
                 }
             }
@@ -83,6 +189,8 @@ impl ToneGenerator {
     }
 
     pub fn start(&mut self) -> Result<(), LocalError> {
+        self.request_sweep_restart();
+
         if let Some(ref mut stream) = self.output_stream {
             stream
                 .play()
@@ -115,9 +223,7 @@ impl ToneGenerator {
             &output_device,
             left_output_channel_index,
             right_output_channel_index,
-            self.sine_mode_enabled.clone(),
-            self.reference_frequency.clone(),
-            self.reference_level.clone(),
+            self.shared_state.clone(),
             user_interface_sender,
         )
         .map_err(|err| LocalError::ToneGeneratorOutputStream(err.to_string()))?;
@@ -130,15 +236,48 @@ impl ToneGenerator {
 
         Ok(())
     }
+
+    pub fn update_guide_stream_on_new_device(&mut self, name: Option<&str>) -> Result<(), LocalError> {
+        self.guide_stream = None;
+
+        let Some(name) = name else {
+            return Ok(());
+        };
+
+        let guide_device = get_output_device_from_device_name(name)?;
+        let guide_stream = create_guide_stream(
+            &guide_device,
+            self.shared_guide_state.clone(),
+            self.user_interface_sender.clone(),
+        )?;
+
+        guide_stream
+            .play()
+            .map_err(|err| LocalError::GuideOutputStream(err.to_string()))?;
+
+        self.guide_stream = Some(guide_stream);
+
+        Ok(())
+    }
+
+    fn silence_guide(&self) {
+        if let Ok(mut guide_delta) = self.shared_guide_state.delta.lock() {
+            *guide_delta = None;
+        }
+    }
+
+    fn request_sweep_restart(&self) {
+        if let Ok(mut sweep_restart_requested) = self.shared_state.sweep_restart_requested.lock() {
+            *sweep_restart_requested = true;
+        }
+    }
 }
 
 fn create_output_steam(
     device: &Device,
     left_channel_index: usize,
     right_channel_index: Option<usize>,
-    sine_mode_enabled: Arc<Mutex<bool>>,
-    reference_frequency: Arc<Mutex<f32>>,
-    reference_level: Arc<Mutex<f32>>,
+    shared_state: SharedToneState,
     user_interface_sender: Sender<EventType>,
 ) -> Result<Stream, LocalError> {
     let config_result = device
@@ -150,6 +289,20 @@ fn create_output_steam(
     let sample_rate = stream_config.sample_rate as f32;
     let mut sine_wave = Sine::new(sample_rate);
     let mut square_wave = Square::new(sample_rate);
+    let mut sweep = Sweep::new(sample_rate);
+    let mut burst = Burst::default();
+
+    let SharedToneState {
+        waveform,
+        output_channels,
+        reference_frequency,
+        reference_level,
+        sweep_settings,
+        sweep_restart_requested,
+        burst_requested,
+        burst_played_producer,
+        sweep_frequency,
+    } = shared_state;
 
     let initial_frequency = match reference_frequency.lock() {
         Ok(frequency) => frequency.to_owned(),
@@ -161,14 +314,24 @@ fn create_output_steam(
         Err(_) => return Err(LocalError::ToneGeneratorInitialization),
     };
 
-    let initial_sine_mode = match sine_mode_enabled.lock() {
-        Ok(sine_mode_enabled) => sine_mode_enabled.to_owned(),
+    let initial_waveform = match waveform.lock() {
+        Ok(waveform) => waveform.to_owned(),
+        Err(_) => return Err(LocalError::ToneGeneratorInitialization),
+    };
+
+    let initial_output_channels = match output_channels.lock() {
+        Ok(output_channels) => output_channels.to_owned(),
+        Err(_) => return Err(LocalError::ToneGeneratorInitialization),
+    };
+
+    let initial_sweep_settings = match sweep_settings.lock() {
+        Ok(settings) => settings.to_owned(),
         Err(_) => return Err(LocalError::ToneGeneratorInitialization),
     };
 
     let mut dbfs_adjustment_factor = get_dbfs_adjustment_factor_from_target_level(initial_level);
 
-    let callback = move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+    let callback = move |data: &mut [f32], info: &cpal::OutputCallbackInfo| {
         let current_frequency = if let Ok(frequency) = reference_frequency.lock() {
             *frequency
         } else {
@@ -181,29 +344,77 @@ fn create_output_steam(
             initial_level
         };
 
-        let current_sine_mode = if let Ok(sine_mode_enabled) = sine_mode_enabled.lock() {
-            *sine_mode_enabled
+        let current_waveform = if let Ok(waveform) = waveform.lock() {
+            *waveform
+        } else {
+            initial_waveform
+        };
+
+        let current_output_channels = if let Ok(output_channels) = output_channels.lock() {
+            *output_channels
+        } else {
+            initial_output_channels
+        };
+
+        let current_sweep_settings = if let Ok(settings) = sweep_settings.lock() {
+            *settings
         } else {
-            initial_sine_mode
+            initial_sweep_settings
         };
 
+        if let Ok(mut restart_requested) = sweep_restart_requested.lock()
+            && *restart_requested
+        {
+            sweep.reset();
+            *restart_requested = false;
+        }
+
+        if current_waveform == Waveform::Burst
+            && let Ok(mut requested) = burst_requested.lock()
+            && *requested
+        {
+            burst.trigger();
+            *requested = false;
+            let played_at = get_playback_instant(Instant::now(), &info.timestamp());
+            if let Ok(mut producer) = burst_played_producer.lock() {
+                let _ = producer.push(played_at);
+            }
+        }
+
         let current_dbfs_factor = get_dbfs_adjustment_factor_from_target_level(current_level);
         if (current_dbfs_factor - dbfs_adjustment_factor).abs() > MINIMUM_DBFS_FACTOR_THRESHOLD {
             dbfs_adjustment_factor = current_dbfs_factor;
         }
 
         for channels in data.chunks_mut(number_of_channels as usize) {
-            let tone_sample = if current_sine_mode {
-                sine_wave.generate_tone_sample(current_frequency, current_level)
-            } else {
-                square_wave.generate_tone_sample(current_frequency, current_level)
+            let tone_sample = match current_waveform {
+                Waveform::Sine => sine_wave.generate_tone_sample(current_frequency, current_level),
+                Waveform::Square => {
+                    square_wave.generate_tone_sample(current_frequency, current_level)
+                }
+                Waveform::Sweep => {
+                    let sweep_frequency = sweep.next_frequency(&current_sweep_settings);
+                    sine_wave.generate_tone_sample(sweep_frequency, current_level)
+                        * sweep.get_fade_gain(&current_sweep_settings)
+                }
+                Waveform::Burst => burst.next_sample(dbfs_adjustment_factor),
             };
 
-            channels[left_channel_index] = tone_sample;
+            channels[left_channel_index] = get_output_sample_for_channel(
+                tone_sample,
+                current_output_channels.is_left_enabled(),
+            );
             if let Some(index) = right_channel_index {
-                channels[index] = tone_sample;
+                channels[index] = get_output_sample_for_channel(
+                    tone_sample,
+                    current_output_channels.is_right_enabled(),
+                );
             }
         }
+
+        if current_waveform == Waveform::Sweep {
+            sweep_frequency.publish(sweep.current_frequency());
+        }
     };
 
     device
@@ -223,6 +434,55 @@ fn create_output_steam(
         .map_err(|err| LocalError::ToneGeneratorOutputStream(err.to_string()))
 }
 
+pub fn create_burst_played_ring_buffer() -> (Producer<Instant>, Consumer<Instant>) {
+    RingBuffer::new(BURST_PLAYED_RING_BUFFER_SIZE)
+}
+
+fn create_guide_stream(
+    device: &Device,
+    shared_guide_state: SharedGuideState,
+    user_interface_sender: Sender<EventType>,
+) -> Result<Stream, LocalError> {
+    let config_result = device
+        .default_output_config()
+        .map_err(|err| LocalError::DeviceConfiguration(err.to_string()))?;
+
+    let stream_config = config_result.config();
+    let number_of_channels = stream_config.channels as usize;
+    let mut guide = Guide::new(stream_config.sample_rate as f32);
+    let SharedGuideState {
+        delta,
+        unity_tolerance,
+    } = shared_guide_state;
+
+    let callback = move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+        let current_delta = delta.lock().map(|delta| *delta).unwrap_or(None);
+        let current_unity_tolerance = unity_tolerance
+            .lock()
+            .map(|tolerance| *tolerance)
+            .unwrap_or_default();
+
+        let beep = get_guide_beep(current_delta, current_unity_tolerance);
+
+        for channels in data.chunks_mut(number_of_channels) {
+            channels.fill(guide.next_sample(beep));
+        }
+    };
+
+    device
+        .build_output_stream(
+            &stream_config,
+            callback,
+            move |error| {
+                let _ = user_interface_sender.send(EventType::RecoverableError(
+                    LocalError::GuideOutputStream(error.to_string()).to_string(),
+                ));
+            },
+            None,
+        )
+        .map_err(|err| LocalError::GuideOutputStream(err.to_string()))
+}
+
 fn get_output_device_from_device_name(device_name: &str) -> Result<Device, LocalError> {
     let host = default_host();
 
@@ -271,6 +531,19 @@ fn get_channel_list_from_output_device(device: &Device) -> Vec<String> {
     }
 }
 
+fn get_playback_instant(callback_instant: Instant, timestamp: &OutputStreamTimestamp) -> Instant {
+    let buffer_delay = timestamp
+        .playback
+        .duration_since(&timestamp.callback)
+        .unwrap_or_default();
+
+    callback_instant + buffer_delay
+}
+
+fn get_output_sample_for_channel(tone_sample: f32, channel_enabled: bool) -> f32 {
+    if channel_enabled { tone_sample } else { 0.0 }
+}
+
 fn get_dbfs_adjustment_factor_from_target_level(level: f32) -> f32 {
     10.0_f32.powf(level / 20.0)
 }
@@ -294,4 +567,30 @@ mod tests {
         let correct_result = 1.0;
         assert_eq!(result, correct_result);
     }
+
+    #[test]
+    fn playback_instant_includes_the_output_buffer_delay() {
+        let callback_instant = Instant::now();
+        let timestamp = OutputStreamTimestamp {
+            callback: cpal::StreamInstant::new(2, 0),
+            playback: cpal::StreamInstant::new(2, 5_000_000),
+        };
+
+        let played_at = get_playback_instant(callback_instant, &timestamp);
+        assert_eq!(played_at - callback_instant, std::time::Duration::from_millis(5));
+    }
+
+    #[test]
+    fn muted_output_channel_gets_silence() {
+        assert_eq!(get_output_sample_for_channel(0.5, true), 0.5);
+        assert_eq!(get_output_sample_for_channel(0.5, false), 0.0);
+    }
+
+    #[test]
+    fn only_the_selected_output_channel_is_enabled() {
+        assert!(OutputChannels::Both.is_left_enabled() && OutputChannels::Both.is_right_enabled());
+        assert!(OutputChannels::LeftOnly.is_left_enabled());
+        assert!(!OutputChannels::LeftOnly.is_right_enabled());
+        assert!(!OutputChannels::RightOnly.is_left_enabled());
+    }
 }
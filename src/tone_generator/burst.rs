@@ -0,0 +1,93 @@
+const MAXIMUM_LENGTH_SEQUENCE_ORDER: u32 = 12;
+const MAXIMUM_LENGTH_SEQUENCE_FEEDBACK_MASK: u32 = 0x829;
+
+pub struct Burst {
+    sequence: Vec<f32>,
+    position: Option<usize>,
+}
+
+impl Default for Burst {
+    fn default() -> Self {
+        Self {
+            sequence: get_maximum_length_sequence(),
+            position: None,
+        }
+    }
+}
+
+impl Burst {
+    pub fn trigger(&mut self) {
+        self.position = Some(0);
+    }
+
+    pub fn next_sample(&mut self, level_factor: f32) -> f32 {
+        let Some(position) = self.position else {
+            return 0.0;
+        };
+
+        match self.sequence.get(position) {
+            Some(sample) => {
+                self.position = Some(position + 1);
+                sample * level_factor
+            }
+            None => {
+                self.position = None;
+                0.0
+            }
+        }
+    }
+}
+
+pub fn get_maximum_length_sequence() -> Vec<f32> {
+    let sequence_length = (1 << MAXIMUM_LENGTH_SEQUENCE_ORDER) - 1;
+    let mut register: u32 = 1;
+
+    (0..sequence_length)
+        .map(|_| {
+            let output_bit = register & 1;
+            register >>= 1;
+            if output_bit == 1 {
+                register ^= MAXIMUM_LENGTH_SEQUENCE_FEEDBACK_MASK;
+            }
+
+            if output_bit == 1 { 1.0 } else { -1.0 }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maximum_length_sequence_is_balanced() {
+        let sequence = get_maximum_length_sequence();
+        let ones = sequence.iter().filter(|sample| **sample > 0.0).count();
+        assert_eq!(sequence.len(), 4095);
+        assert_eq!(ones, 2048);
+    }
+
+    #[test]
+    fn maximum_length_sequence_has_flat_circular_autocorrelation() {
+        let sequence = get_maximum_length_sequence();
+        let length = sequence.len();
+        for lag in [1, 2, 100, 2047] {
+            let correlation: f32 = (0..length)
+                .map(|index| sequence[index] * sequence[(index + lag) % length])
+                .sum();
+            assert_eq!(correlation, -1.0);
+        }
+    }
+
+    #[test]
+    fn burst_plays_the_sequence_once_after_being_triggered() {
+        let mut burst = Burst::default();
+        assert_eq!(burst.next_sample(0.5), 0.0);
+
+        burst.trigger();
+        let played: Vec<f32> = (0..4095).map(|_| burst.next_sample(0.5)).collect();
+        assert!(played.iter().all(|sample| sample.abs() == 0.5));
+        assert_eq!(burst.next_sample(0.5), 0.0);
+        assert_eq!(burst.next_sample(0.5), 0.0);
+    }
+}
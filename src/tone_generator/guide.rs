@@ -0,0 +1,148 @@
+const RADS_PER_CYCLE: f32 = 2.0 * std::f32::consts::PI;
+const GUIDE_LEVEL: f32 = -20.0;
+const LOW_FREQUENCY: f32 = 440.0;
+const UNITY_FREQUENCY: f32 = 880.0;
+const HIGH_FREQUENCY: f32 = 1320.0;
+const BEEP_LENGTH_SECONDS: f32 = 0.06;
+const BEEP_RAMP_SECONDS: f32 = 0.005;
+const MINIMUM_BEEP_PERIOD_SECONDS: f32 = 0.12;
+const MAXIMUM_BEEP_PERIOD_SECONDS: f32 = 1.0;
+const BEEP_PERIOD_SECONDS_PER_DB: f32 = 0.075;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GuideChannel {
+    #[default]
+    Off,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GuideBeep {
+    pub frequency: f32,
+    pub period_seconds: Option<f32>,
+}
+
+pub struct Guide {
+    sample_rate: f32,
+    amplitude: f32,
+    phase: f32,
+    samples_into_period: usize,
+}
+
+impl Guide {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate,
+            amplitude: 10.0_f32.powf(GUIDE_LEVEL / 20.0),
+            phase: 0.0,
+            samples_into_period: 0,
+        }
+    }
+
+    pub fn next_sample(&mut self, beep: Option<GuideBeep>) -> f32 {
+        let Some(beep) = beep else {
+            self.phase = 0.0;
+            self.samples_into_period = 0;
+            return 0.0;
+        };
+
+        let envelope = match beep.period_seconds {
+            Some(period_seconds) => {
+                let envelope = get_beep_envelope(self.samples_into_period, self.sample_rate);
+                self.samples_into_period += 1;
+                if self.samples_into_period as f32 >= period_seconds * self.sample_rate {
+                    self.samples_into_period = 0;
+                }
+                envelope
+            }
+            None => 1.0,
+        };
+
+        self.phase += RADS_PER_CYCLE * beep.frequency / self.sample_rate;
+        if self.phase >= RADS_PER_CYCLE {
+            self.phase -= RADS_PER_CYCLE;
+        }
+
+        self.phase.sin() * envelope * self.amplitude
+    }
+}
+
+// Beeps sound low when the input is below unity and high when it is above, and come
+// faster as the level closes in. Within the unity tolerance the tone holds steady.
+pub fn get_guide_beep(delta: Option<f32>, unity_tolerance: f32) -> Option<GuideBeep> {
+    let delta = delta.filter(|delta| delta.is_finite())?;
+
+    if delta.abs() <= unity_tolerance {
+        return Some(GuideBeep {
+            frequency: UNITY_FREQUENCY,
+            period_seconds: None,
+        });
+    }
+
+    let frequency = if delta < 0.0 {
+        LOW_FREQUENCY
+    } else {
+        HIGH_FREQUENCY
+    };
+    let period_seconds = (delta.abs() * BEEP_PERIOD_SECONDS_PER_DB)
+        .clamp(MINIMUM_BEEP_PERIOD_SECONDS, MAXIMUM_BEEP_PERIOD_SECONDS);
+
+    Some(GuideBeep {
+        frequency,
+        period_seconds: Some(period_seconds),
+    })
+}
+
+fn get_beep_envelope(samples_into_period: usize, sample_rate: f32) -> f32 {
+    let beep_length = (BEEP_LENGTH_SECONDS * sample_rate).round() as usize;
+    if samples_into_period >= beep_length {
+        return 0.0;
+    }
+
+    let ramp_length = (BEEP_RAMP_SECONDS * sample_rate).max(1.0);
+    let from_start = samples_into_period as f32 / ramp_length;
+    let to_end = (beep_length - samples_into_period) as f32 / ramp_length;
+    from_start.min(to_end).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn beep_low_below_unity_high_above_and_hold_steady_at_unity() {
+        let low = get_guide_beep(Some(-3.0), 0.1).unwrap();
+        let high = get_guide_beep(Some(3.0), 0.1).unwrap();
+        let unity = get_guide_beep(Some(0.05), 0.1).unwrap();
+
+        assert_eq!(low.frequency, LOW_FREQUENCY);
+        assert_eq!(high.frequency, HIGH_FREQUENCY);
+        assert_eq!(low.period_seconds, high.period_seconds);
+        assert_eq!(unity.frequency, UNITY_FREQUENCY);
+        assert_eq!(unity.period_seconds, None);
+        assert_eq!(get_guide_beep(None, 0.1), None);
+        assert_eq!(get_guide_beep(Some(f32::NEG_INFINITY), 0.1), None);
+    }
+
+    #[test]
+    fn beep_faster_as_the_level_closes_in_on_unity() {
+        let period = |delta| get_guide_beep(Some(delta), 0.1).unwrap().period_seconds.unwrap();
+
+        assert_eq!(period(0.5), MINIMUM_BEEP_PERIOD_SECONDS);
+        assert!(period(2.0) < period(6.0));
+        assert_eq!(period(-20.0), MAXIMUM_BEEP_PERIOD_SECONDS);
+    }
+
+    #[test]
+    fn stay_silent_between_beeps_and_without_a_signal() {
+        let sample_rate = 1000.0;
+        let mut guide = Guide::new(sample_rate);
+        let beep = get_guide_beep(Some(-4.0), 0.1);
+        let samples: Vec<f32> = (0..300).map(|_| guide.next_sample(beep)).collect();
+
+        assert!(samples[..60].iter().any(|sample| sample.abs() > 0.05));
+        assert!(samples[60..300].iter().all(|sample| *sample == 0.0));
+        assert!((0..100).all(|_| guide.next_sample(None) == 0.0));
+    }
+}
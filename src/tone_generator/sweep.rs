@@ -0,0 +1,203 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+const FADE_DURATION_IN_SECONDS: f32 = 0.05;
+const MINIMUM_SWEEP_FREQUENCY: f32 = 1.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepSettings {
+    pub start_frequency: f32,
+    pub stop_frequency: f32,
+    pub duration_in_seconds: f32,
+    pub fade_enabled: bool,
+    pub loop_enabled: bool,
+}
+
+impl Default for SweepSettings {
+    fn default() -> Self {
+        Self {
+            start_frequency: 20.0,
+            stop_frequency: 20000.0,
+            duration_in_seconds: 10.0,
+            fade_enabled: true,
+            loop_enabled: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SharedSweepFrequency {
+    frequency_bits: Arc<AtomicU32>,
+}
+
+impl SharedSweepFrequency {
+    pub fn publish(&self, frequency: f32) {
+        self.frequency_bits.store(frequency.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> f32 {
+        f32::from_bits(self.frequency_bits.load(Ordering::Relaxed))
+    }
+}
+
+pub struct Sweep {
+    sample_rate: f32,
+    elapsed_samples: u64,
+    current_frequency: f32,
+}
+
+impl Sweep {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate,
+            elapsed_samples: 0,
+            current_frequency: 0.0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.elapsed_samples = 0;
+    }
+
+    pub fn current_frequency(&self) -> f32 {
+        self.current_frequency
+    }
+
+    pub fn is_finished(&self, settings: &SweepSettings) -> bool {
+        !settings.loop_enabled && self.elapsed_samples >= self.get_total_samples(settings)
+    }
+
+    pub fn next_frequency(&mut self, settings: &SweepSettings) -> f32 {
+        let total_samples = self.get_total_samples(settings);
+
+        if self.elapsed_samples >= total_samples {
+            if !settings.loop_enabled {
+                return self.current_frequency;
+            }
+            self.elapsed_samples = 0;
+        }
+
+        let position = self.elapsed_samples as f32 / total_samples as f32;
+        self.current_frequency = get_exponential_sweep_frequency_at_position(
+            settings.start_frequency,
+            settings.stop_frequency,
+            position,
+        );
+        self.elapsed_samples += 1;
+
+        self.current_frequency
+    }
+
+    pub fn get_fade_gain(&self, settings: &SweepSettings) -> f32 {
+        if self.is_finished(settings) {
+            return 0.0;
+        }
+
+        if !settings.fade_enabled {
+            return 1.0;
+        }
+
+        let fade_samples = (FADE_DURATION_IN_SECONDS * self.sample_rate).max(1.0);
+        let samples_from_start = self.elapsed_samples as f32;
+        let samples_to_end = self
+            .get_total_samples(settings)
+            .saturating_sub(self.elapsed_samples) as f32;
+
+        let fade_position = (samples_from_start.min(samples_to_end) / fade_samples).min(1.0);
+
+        get_raised_cosine_gain(fade_position)
+    }
+
+    fn get_total_samples(&self, settings: &SweepSettings) -> u64 {
+        ((settings.duration_in_seconds * self.sample_rate) as u64).max(1)
+    }
+}
+
+pub fn get_exponential_sweep_frequency_at_position(
+    start_frequency: f32,
+    stop_frequency: f32,
+    position: f32,
+) -> f32 {
+    let start = start_frequency.max(MINIMUM_SWEEP_FREQUENCY);
+    let stop = stop_frequency.max(MINIMUM_SWEEP_FREQUENCY);
+    start * (stop / start).powf(position.clamp(0.0, 1.0))
+}
+
+fn get_raised_cosine_gain(position: f32) -> f32 {
+    0.5 - 0.5 * (std::f32::consts::PI * position).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_sweep_frequency_returns_the_last_published_frequency_to_every_clone() {
+        let published = SharedSweepFrequency::default();
+        let polled = published.clone();
+        assert_eq!(polled.get(), 0.0);
+
+        published.publish(997.5);
+        assert_eq!(polled.get(), 997.5);
+    }
+
+    #[test]
+    fn return_start_and_stop_frequency_at_sweep_boundaries() {
+        let start = get_exponential_sweep_frequency_at_position(20.0, 20000.0, 0.0);
+        let stop = get_exponential_sweep_frequency_at_position(20.0, 20000.0, 1.0);
+        assert!((start - 20.0).abs() < 1e-3);
+        assert!((stop - 20000.0).abs() < 1e-1);
+    }
+
+    #[test]
+    fn return_geometric_mean_frequency_at_sweep_midpoint() {
+        let middle = get_exponential_sweep_frequency_at_position(100.0, 10000.0, 0.5);
+        assert!((middle - 1000.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn sweep_stops_at_stop_frequency_when_loop_is_disabled() {
+        let settings = SweepSettings {
+            start_frequency: 100.0,
+            stop_frequency: 1000.0,
+            duration_in_seconds: 1.0,
+            fade_enabled: false,
+            loop_enabled: false,
+        };
+        let mut sweep = Sweep::new(100.0);
+
+        for _ in 0..150 {
+            sweep.next_frequency(&settings);
+        }
+
+        assert!(sweep.is_finished(&settings));
+        assert_eq!(sweep.get_fade_gain(&settings), 0.0);
+    }
+
+    #[test]
+    fn sweep_restarts_at_start_frequency_when_loop_is_enabled() {
+        let settings = SweepSettings {
+            start_frequency: 100.0,
+            stop_frequency: 1000.0,
+            duration_in_seconds: 1.0,
+            fade_enabled: false,
+            loop_enabled: true,
+        };
+        let mut sweep = Sweep::new(100.0);
+
+        for _ in 0..100 {
+            sweep.next_frequency(&settings);
+        }
+        let frequency = sweep.next_frequency(&settings);
+
+        assert!(!sweep.is_finished(&settings));
+        assert!((frequency - 100.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn return_zero_fade_gain_at_start_of_faded_sweep() {
+        let settings = SweepSettings::default();
+        let sweep = Sweep::new(48000.0);
+        assert_eq!(sweep.get_fade_gain(&settings), 0.0);
+    }
+}
@@ -1,12 +1,73 @@
-use super::AppWindow;
-use crate::device_manager::{CurrentDevice, DeviceList};
-use crate::errors::{EXIT_CODE_ERROR, LocalError};
+use super::{
+    AppWindow, BarMeterReadout, BarMeterScale, BarMeterTick, CheckResult, ClipReadout, CrosstalkReadout, DcOffsetReadout, DistortionReadout, InputFrequencyReadout,
+    LatencyReadout, LevelReadout, LevelStatus, NoiseFloorReadout, PolarityReadout, StereoImageReadout, UnityCheckRow,
+};
+use crate::analysis::get_dbfs_from_amplitude;
+use crate::analysis::distortion::{DistortionMeasurement, DistortionSettings};
+use crate::analysis::fft::WindowFunction;
+use crate::analysis::frequency::is_frequency_matching_reference;
+use crate::analysis::hum::HumMeasurement;
+use crate::analysis::spectrum::{SPECTRUM_FFT_SIZES, Spectrum, SpectrumSettings};
+use crate::analysis::stereo_image::StereoImage;
+use crate::calibration::{
+    AnalogUnit, Calibration, CalibrationDirection, format_analog_level_for_display,
+    format_calibration_for_display, get_calibration_from_text,
+};
+use crate::device_manager::{CurrentDevice, DeviceList, get_device_with_saved_channels};
+use crate::errors::{EXIT_CODE_ERROR, LocalError, handle_local_error};
 use crate::events::EventType;
+use crate::level_meter::clip_detector::{ClipCount, ClipSettings};
+use crate::measurement::MeasurementRequest;
+use crate::measurement::crosstalk::CrosstalkSettings;
+use crate::measurement::frequency_response::{
+    FrequencyResponse, FrequencyResponseSettings, write_frd_file,
+};
+use crate::measurement::gain_drift::{
+    GainDriftLog, GainDriftSettings, export_gain_drift_csv_file, format_elapsed_time,
+};
+use crate::measurement::latency::LatencySettings;
+use crate::measurement::noise_floor::NoiseFloorSettings;
+use crate::measurement::polarity::PolaritySettings;
+use crate::measurement::unity_check::{
+    DEFAULT_UNITY_CHECK_FREQUENCIES, UnityCheckSettings, get_frequencies_from_text,
+    get_tolerance_from_text,
+};
+use crate::settings::preset::{
+    get_preset_file_path, get_preset_names, get_presets_directory, read_preset_file,
+    write_preset_file,
+};
+use crate::settings::{ColourTheme, DEFAULT_UNITY_TOLERANCE, MeterDisplay, Settings, WindowGeometry, get_settings_file_path, write_settings_file};
+use crate::tone_generator::{OutputChannels, Waveform};
+use crate::tone_generator::guide::GuideChannel;
+use crate::tone_generator::sweep::{SharedSweepFrequency, SweepSettings};
 use crossbeam_channel::{Receiver, Sender};
-use slint::{ModelRc, SharedString, VecModel, Weak};
+use bar_meter::{PeakHold, get_bar_meter_range};
+use keep_awake::KeepAwake;
+use measurements::{
+    format_deviation_for_display, format_drift_for_display, get_check_result_from_deviation,
+    get_crosstalk_readout_from_channel, get_gain_drift_graph_scale, get_gain_drift_grid_commands,
+    get_latency_readout_from_result, get_noise_floor_readout_from_channel,
+    get_polarity_readout_from_result, get_unity_check_rows_from_result,
+};
+use graph::{
+    GraphScale, get_grid_path_commands, get_path_commands_from_points,
+    get_peak_points_per_graph_column,
+};
+use slint::{
+    CloseRequestResponse, ComponentHandle, Model, ModelRc, PhysicalPosition, PhysicalSize, SharedString, VecModel, Weak,
+};
+use std::cell::RefCell;
+use std::env;
 use std::error::Error;
+use std::path::{Path, PathBuf};
 use std::process::exit;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+mod bar_meter;
+mod graph;
+mod keep_awake;
+mod measurements;
 
 const FATAL_ERROR_MESSAGE_UI_ERROR: &str =
     "A fatal error has occurred in the UI. The application will now exit.";
@@ -14,10 +75,100 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const DESCRIPTION: &str = env!("CARGO_PKG_DESCRIPTION");
 pub const LICENSE: &str = env!("CARGO_PKG_LICENSE");
 
+const FREQUENCY_RESPONSE_GRAPH_SCALE: GraphScale = GraphScale {
+    minimum_frequency: 20.0,
+    maximum_frequency: 20000.0,
+    minimum_level: -1.0,
+    maximum_level: 1.0,
+    logarithmic_frequency: true,
+};
+const FREQUENCY_RESPONSE_GRID_FREQUENCIES: [f32; 3] = [100.0, 1000.0, 10000.0];
+const FREQUENCY_RESPONSE_GRID_LEVELS: [f32; 5] = [-0.5, -0.1, 0.0, 0.1, 0.5];
+const SPECTRUM_MINIMUM_LEVEL: f32 = -140.0;
+const SPECTRUM_MAXIMUM_LEVEL: f32 = 0.0;
+const SPECTRUM_LOGARITHMIC_GRID_FREQUENCIES: [f32; 3] = [100.0, 1000.0, 10000.0];
+const SPECTRUM_LINEAR_GRID_FREQUENCIES: [f32; 3] = [5000.0, 10000.0, 15000.0];
+const SPECTRUM_GRID_LEVELS: [f32; 6] = [-20.0, -40.0, -60.0, -80.0, -100.0, -120.0];
+const SPECTRUM_WINDOW_FUNCTIONS: [WindowFunction; 4] = [
+    WindowFunction::Rectangular,
+    WindowFunction::Hann,
+    WindowFunction::BlackmanHarris,
+    WindowFunction::FlatTop,
+];
+const HUM_WARNING_LEFT_CHANNEL_TEXT: &str = "left input";
+const HUM_WARNING_RIGHT_CHANNEL_TEXT: &str = "right input";
+const HUM_WARNING_BOTH_CHANNELS_TEXT: &str = "left and right inputs";
+const HUM_WARNING_ADVICE_TEXT: &str = "Check for a ground loop before trusting the reading.";
+const GAIN_DRIFT_FILE_NAME: &str = "sug_gain_drift.csv";
+const GAIN_DRIFT_LOG_FILE_NAME: &str = "gain_drift_log.csv";
+const GAIN_DRIFT_GRAPH_COLUMNS: usize = 500;
+const GAIN_DRIFT_MAXIMUM_GRAPH_POINTS: usize = 4 * GAIN_DRIFT_GRAPH_COLUMNS;
+const FREQUENCY_RESPONSE_FLATNESS_TOLERANCE: f32 = 0.1;
+const LEVEL_STATUS_ROUNDING_MARGIN: f32 = 1e-4;
+const SWEEP_FREQUENCY_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const TOLERANCE_OFF_TEXT: &str = "Off";
+const ANALOG_UNITS: [AnalogUnit; 4] = [
+    AnalogUnit::Dbfs,
+    AnalogUnit::Dbu,
+    AnalogUnit::Dbv,
+    AnalogUnit::VoltsRms,
+];
+const CALIBRATION_MISSING_TEXT: &str = "Not calibrated";
+const SECONDARY_PEAK_LABEL: &str = "Peak";
+const SECONDARY_DELTA_LABEL: &str = "Delta";
+const METER_DISPLAYS: [MeterDisplay; 3] = [MeterDisplay::Numbers, MeterDisplay::Bars, MeterDisplay::Both];
+const COLOUR_THEMES: [ColourTheme; 5] = [
+    ColourTheme::Dark,
+    ColourTheme::Light,
+    ColourTheme::HighContrast,
+    ColourTheme::BlueOrange,
+    ColourTheme::TealPink,
+];
+const GUIDE_CHANNELS: [GuideChannel; 3] = [GuideChannel::Off, GuideChannel::Left, GuideChannel::Right];
+const GUIDE_SAME_DEVICE_TEXT: &str =
+    "Pick a device other than the reference output so the beeps stay out of the tone path.";
+const FREQUENCY_RESPONSE_LEFT_FILE_NAME: &str = "sug_frequency_response_left.frd";
+const FREQUENCY_RESPONSE_RIGHT_FILE_NAME: &str = "sug_frequency_response_right.frd";
+
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 struct State {
     meter_delta_mode_active: bool,
-    reference_level: i32,
+    reference_level: f32,
+    reference_frequency: f32,
+    waveform: Waveform,
+    unity_tolerance: f32,
+    close_tolerance: Option<f32>,
+    analog_unit: AnalogUnit,
+    input_calibration: Option<Calibration>,
+    output_calibration: Option<Calibration>,
+    meter_display: MeterDisplay,
+    guide_channel: GuideChannel,
+    spectrum_logarithmic_frequency: bool,
+    measurement_running: bool,
+}
+
+impl State {
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.reference_frequency = settings.reference_frequency;
+        self.reference_level = settings.reference_level;
+        self.waveform = settings.waveform;
+        self.meter_delta_mode_active = settings.delta_mode_active;
+        self.unity_tolerance = settings.unity_tolerance;
+        self.close_tolerance = settings.close_tolerance;
+        self.analog_unit = settings.analog_unit;
+        self.meter_display = settings.meter_display;
+    }
+
+    fn update_settings(&self, settings: &mut Settings) {
+        settings.reference_frequency = self.reference_frequency;
+        settings.reference_level = self.reference_level;
+        settings.waveform = self.waveform;
+        settings.delta_mode_active = self.meter_delta_mode_active;
+        settings.unity_tolerance = self.unity_tolerance;
+        settings.close_tolerance = self.close_tolerance;
+        settings.analog_unit = self.analog_unit;
+        settings.meter_display = self.meter_display;
+    }
 }
 
 pub struct UI {
@@ -25,11 +176,16 @@ pub struct UI {
     level_meter_sender: Sender<EventType>,
     tone_generator_sender: Sender<EventType>,
     user_interface_sender: Sender<EventType>,
+    measurement_sender: Sender<EventType>,
+    sweep_frequency: SharedSweepFrequency,
     input_device_list: DeviceList,
     output_device_list: DeviceList,
     current_input_device: CurrentDevice,
     current_output_device: CurrentDevice,
+    frequency_response: FrequencyResponse,
+    gain_drift_log: GainDriftLog,
     state: Arc<Mutex<State>>,
+    settings: Arc<Mutex<Settings>>,
 }
 
 impl UI {
@@ -38,6 +194,8 @@ impl UI {
         tone_generator_sender: Sender<EventType>,
         level_meter_sender: Sender<EventType>,
         user_interface_sender: Sender<EventType>,
+        measurement_sender: Sender<EventType>,
+        sweep_frequency: SharedSweepFrequency,
     ) -> Self {
         let ui_weak_mutex = ui_mutex
             .lock()
@@ -50,11 +208,16 @@ impl UI {
             tone_generator_sender,
             level_meter_sender,
             user_interface_sender,
+            measurement_sender,
+            sweep_frequency,
             input_device_list: DeviceList::default(),
             output_device_list: DeviceList::default(),
             current_input_device: CurrentDevice::default(),
             current_output_device: CurrentDevice::default(),
+            frequency_response: FrequencyResponse::default(),
+            gain_drift_log: GainDriftLog::default(),
             state: Arc::new(Mutex::new(State::default())),
+            settings: Arc::new(Mutex::new(Settings::default())),
         }
     }
 
@@ -65,31 +228,235 @@ impl UI {
         let ui_weak = self.ui.clone();
 
         let state_arc = self.state.clone();
+        let mut left_peak_hold = PeakHold::default();
+        let mut right_peak_hold = PeakHold::default();
+        let mut displayed_sweep_frequency = self.sweep_frequency.get();
+        let mut sweep_frequency_polled_at = Instant::now();
 
         loop {
-            if let Ok(event) = level_meter_display_receiver.recv() {
+            if sweep_frequency_polled_at.elapsed() >= SWEEP_FREQUENCY_POLL_INTERVAL {
+                sweep_frequency_polled_at = Instant::now();
+                let frequency = self.sweep_frequency.get();
+
+                if frequency != displayed_sweep_frequency {
+                    displayed_sweep_frequency = frequency;
+                    let frequency_formatted = format_sweep_frequency_for_display(frequency);
+
+                    let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                        ui.set_sweep_current_frequency(SharedString::from(frequency_formatted));
+                    });
+                }
+            }
+
+            if let Ok(event) = level_meter_display_receiver.recv_timeout(SWEEP_FREQUENCY_POLL_INTERVAL) {
                 match event {
                     EventType::MeterLevelUpdate {
                         mut left,
                         mut right,
                     } => {
-                        let state = state_arc
+                        let state = *state_arc
                             .lock()
                             .unwrap_or_else(std::sync::PoisonError::into_inner);
 
+                        if state.guide_channel != GuideChannel::Off {
+                            let _ = self.tone_generator_sender.send(EventType::GuideLevelUpdate {
+                                delta: get_guide_delta(left, right, &state),
+                                unity_tolerance: state.unity_tolerance,
+                            });
+                        }
+
+                        let left_secondary_level = get_secondary_level_text(left, &state);
+                        let right_secondary_level = get_secondary_level_text(right, &state);
+                        let now = Instant::now();
+                        let mut left_peak = left_peak_hold.update(left, now);
+                        let mut right_peak = right_peak_hold.update(right, now);
+
                         if state.meter_delta_mode_active {
-                            left -= state.reference_level as f32;
-                            right -= state.reference_level as f32;
+                            left -= state.reference_level;
+                            right -= state.reference_level;
+                            left_peak -= state.reference_level;
+                            right_peak -= state.reference_level;
                         }
 
-                        let left_formatted = format_peak_delta_values_for_display(left);
-                        let right_formatted = format_peak_delta_values_for_display(right);
+                        let left_readout = get_level_readout(left, &state);
+                        let right_readout = get_level_readout(right, &state);
+                        let left_bar_meter =
+                            get_bar_meter_readout(left, left_peak, left_readout.status, &state);
+                        let right_bar_meter =
+                            get_bar_meter_readout(right, right_peak, right_readout.status, &state);
+
+                        let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                            ui.set_left_level(left_readout);
+                            ui.set_right_level(right_readout);
+                            ui.set_left_bar_meter(left_bar_meter);
+                            ui.set_right_bar_meter(right_bar_meter);
+                            ui.set_left_secondary_level(SharedString::from(left_secondary_level));
+                            ui.set_right_secondary_level(SharedString::from(right_secondary_level));
+                        });
+                    }
+                    EventType::MeterDistortionUpdate { left, right } => {
+                        let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                            ui.set_distortion_left(get_distortion_readout_from_measurement(left));
+                            ui.set_distortion_right(get_distortion_readout_from_measurement(right));
+                        });
+                    }
+                    EventType::MeterFrequencyUpdate { left, right } => {
+                        let state = *state_arc
+                            .lock()
+                            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+                        let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                            ui.set_left_input_frequency(get_input_frequency_readout(left, &state));
+                            ui.set_right_input_frequency(get_input_frequency_readout(right, &state));
+                        });
+                    }
+                    EventType::MeterDcOffsetUpdate { left, right } => {
+                        let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                            ui.set_left_dc_offset(get_dc_offset_readout(left));
+                            ui.set_right_dc_offset(get_dc_offset_readout(right));
+                        });
+                    }
+                    EventType::MeterClipUpdate { left, right } => {
+                        let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                            ui.set_left_clip(get_clip_readout_from_count(left));
+                            ui.set_right_clip(get_clip_readout_from_count(right));
+                        });
+                    }
+                    EventType::MeterHumUpdate { left, right } => {
+                        let warning = get_hum_warning_text(left, right).unwrap_or_default();
+
+                        let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                            ui.set_hum_warning(SharedString::from(warning));
+                        });
+                    }
+                    EventType::MeterSpectrumUpdate(spectrum) => {
+                        let logarithmic_frequency = state_arc
+                            .lock()
+                            .unwrap_or_else(std::sync::PoisonError::into_inner)
+                            .spectrum_logarithmic_frequency;
+
+                        let scale = get_spectrum_graph_scale(logarithmic_frequency);
+                        let (left_commands, right_commands) =
+                            get_spectrum_path_commands(&spectrum, &scale);
+
+                        let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                            ui.set_spectrum_left_commands(SharedString::from(left_commands));
+                            ui.set_spectrum_right_commands(SharedString::from(right_commands));
+                        });
+                    }
+                    EventType::MeterStereoImageUpdate(stereo_image) => {
+                        let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                            ui.set_stereo_image(get_stereo_image_readout_from_measurement(stereo_image));
+                        });
+                    }
+                    EventType::MeasurementRunningUpdate(running) => {
+                        state_arc
+                            .lock()
+                            .unwrap_or_else(std::sync::PoisonError::into_inner)
+                            .measurement_running = running;
+
+                        let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                            ui.set_measurement_running(running);
+                            ui.set_start_button_active(running);
+                        });
 
+                        if !running {
+                            self.restore_tone_generator_settings();
+                        }
+                    }
+                    EventType::MeasurementProgressUpdate(message) => {
+                        let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                            ui.set_measurement_progress(SharedString::from(message));
+                        });
+                    }
+                    EventType::FrequencyResponseUpdate(frequency_response) => {
+                        self.frequency_response = frequency_response;
+                        self.update_frequency_response_display_data();
+                    }
+                    EventType::FrequencyResponseExport(directory) => {
+                        if let Err(error) = self.export_frequency_response(&directory) {
+                            handle_error_in_ui(&ui_weak, &error.to_string());
+                        }
+                    }
+                    EventType::UnityCheckUpdate(unity_check_result) => {
+                        let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                            ui.set_unity_check_rows(ModelRc::new(VecModel::from(
+                                get_unity_check_rows_from_result(&unity_check_result),
+                            )));
+                        });
+                    }
+                    EventType::NoiseFloorUpdate(noise_floor_result) => {
+                        let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                            ui.set_noise_floor_left(get_noise_floor_readout_from_channel(
+                                noise_floor_result.left,
+                            ));
+                            ui.set_noise_floor_right(get_noise_floor_readout_from_channel(
+                                noise_floor_result.right,
+                            ));
+                        });
+                    }
+                    EventType::CrosstalkUpdate(crosstalk_result) => {
+                        let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                            ui.set_crosstalk_left_driven(get_crosstalk_readout_from_channel(
+                                crosstalk_result.left_driven,
+                            ));
+                            ui.set_crosstalk_right_driven(get_crosstalk_readout_from_channel(
+                                crosstalk_result.right_driven,
+                            ));
+                        });
+                    }
+                    EventType::LatencyUpdate(latency_result) => {
+                        let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                            ui.set_latency_readout(get_latency_readout_from_result(&latency_result));
+                        });
+                    }
+                    EventType::GainDriftStart => {
+                        self.gain_drift_log = GainDriftLog::default();
+                        self.update_gain_drift_display_data();
+                    }
+                    EventType::GainDriftUpdate(gain_drift_point) => {
+                        self.gain_drift_log.points.push(gain_drift_point);
+                        if self.gain_drift_log.points.len() > GAIN_DRIFT_MAXIMUM_GRAPH_POINTS {
+                            self.gain_drift_log.decimate(GAIN_DRIFT_GRAPH_COLUMNS);
+                        }
+                        self.update_gain_drift_display_data();
+                    }
+                    EventType::GainDriftExport(directory) => {
+                        if let Err(error) = self.export_gain_drift(&directory) {
+                            handle_error_in_ui(&ui_weak, &error.to_string());
+                        }
+                    }
+                    EventType::PolarityUpdate(polarity_result) => {
                         let _ = ui_weak.upgrade_in_event_loop(move |ui| {
-                            ui.set_left_level_box_value(SharedString::from(left_formatted));
-                            ui.set_right_level_box_value(SharedString::from(right_formatted));
+                            ui.set_polarity_readout(get_polarity_readout_from_result(&polarity_result));
                         });
                     }
+                    EventType::PresetLoad(name) => {
+                        if let Err(error) = self.load_preset(&name) {
+                            handle_error_in_ui(&ui_weak, &error.to_string());
+                        }
+                    }
+                    EventType::PresetSave(name) => {
+                        if let Err(error) = self.save_preset(&name) {
+                            handle_error_in_ui(&ui_weak, &error.to_string());
+                        }
+                    }
+                    EventType::PresetImport(file_path) => {
+                        if let Err(error) = self.import_preset(&file_path) {
+                            handle_error_in_ui(&ui_weak, &error.to_string());
+                        }
+                    }
+                    EventType::PresetExport { name, directory } => {
+                        if let Err(error) = self.export_preset(&name, &directory) {
+                            handle_error_in_ui(&ui_weak, &error.to_string());
+                        }
+                    }
+                    EventType::CalibrationUpdate {
+                        direction,
+                        calibration,
+                    } => {
+                        self.update_calibration(direction, calibration);
+                    }
                     EventType::RecoverableError(error) => {
                         handle_error_in_ui(&ui_weak, error.as_str());
                     }
@@ -107,6 +474,8 @@ impl UI {
                         }) {
                             handle_error_in_ui(&ui_weak, &error.to_string());
                         }
+
+                        self.remember_current_devices();
                     }
                     EventType::OutputDeviceUpdate(device_name) => {
                         self.update_current_output_device(device_name.clone())?;
@@ -121,6 +490,9 @@ impl UI {
                         {
                             handle_error_in_ui(&ui_weak, &error.to_string());
                         }
+
+                        self.release_guide_device_when_it_is_the_reference_output();
+                        self.remember_current_devices();
                     }
                     EventType::InputChannelUpdate { left, right } => {
                         self.current_input_device.left_channel = left.clone();
@@ -135,6 +507,8 @@ impl UI {
                         {
                             handle_error_in_ui(&ui_weak, &error.to_string());
                         }
+
+                        self.remember_current_devices();
                     }
                     EventType::OutputChannelUpdate { left, right } => {
                         self.current_output_device.left_channel = left.clone();
@@ -150,6 +524,8 @@ impl UI {
                         {
                             handle_error_in_ui(&ui_weak, &error.to_string());
                         }
+
+                        self.remember_current_devices();
                     }
                     EventType::InputDeviceListUpdate(input_device_list) => {
                         self.input_device_list = input_device_list.clone();
@@ -175,6 +551,8 @@ impl UI {
                             {
                                 handle_error_in_ui(&ui_weak, &error.to_string());
                             }
+
+                            self.remember_current_devices();
                         }
 
                         self.initialize_displayed_input_device_data()?;
@@ -202,6 +580,9 @@ impl UI {
                             {
                                 handle_error_in_ui(&ui_weak, &error.to_string());
                             }
+
+                            self.release_guide_device_when_it_is_the_reference_output();
+                            self.remember_current_devices();
                         }
 
                         self.initialize_displayed_output_device_data()?;
@@ -221,24 +602,69 @@ impl UI {
         &mut self,
         current_input_device: CurrentDevice,
         current_output_device: CurrentDevice,
-        reference_frequency: f32,
-        reference_level: i32,
-        delta_mode_active: bool,
+        settings: &Settings,
+        sweep_settings: SweepSettings,
     ){
         self.current_input_device = current_input_device;
         self.current_output_device = current_output_device;
 
-        {
+        *self
+            .settings
+            .lock()
+            .unwrap_or_else(|poisoned| {
+                poisoned.into_inner()
+            }) = settings.clone();
+
+        let reference_frequency = settings.reference_frequency;
+        let reference_level = settings.reference_level;
+        let waveform = settings.waveform;
+        let delta_mode_active = settings.delta_mode_active;
+        let unity_tolerance = format_tolerance_for_display(Some(settings.unity_tolerance));
+        let close_tolerance = format_tolerance_for_display(settings.close_tolerance);
+        let analog_unit = settings.analog_unit;
+        let meter_display = settings.meter_display;
+        let colour_theme = settings.colour_theme;
+        let window_geometry = settings.window_geometry;
+
+        let state = {
             let mut state = self
                 .state
                 .lock()
                 .unwrap_or_else(|poisoned| {
                     poisoned.into_inner()
                 });
-            state.reference_level = reference_level;
-            state.meter_delta_mode_active = delta_mode_active;
+            state.apply_settings(settings);
+            state.spectrum_logarithmic_frequency = true;
+            *state
+        };
+
+        self.remember_current_devices();
+
+        self.restore_tone_generator_settings();
+        self.update_preset_list_display_data(None);
+
+        if let Err(error) = self
+            .level_meter_sender
+            .send(EventType::MeterReferenceFrequencyUpdate(reference_frequency))
+        {
+            handle_error_in_ui(&self.ui, &error.to_string());
         }
 
+        let frequency_response_grid_commands = get_grid_path_commands(
+            &FREQUENCY_RESPONSE_GRAPH_SCALE,
+            &FREQUENCY_RESPONSE_GRID_FREQUENCIES,
+            &FREQUENCY_RESPONSE_GRID_LEVELS,
+        );
+        let export_directory = get_default_export_directory();
+        let distortion_settings = DistortionSettings::default();
+        let spectrum_settings = SpectrumSettings::default();
+        let spectrum_grid_commands = get_spectrum_grid_commands(true);
+        let unity_check_frequencies = DEFAULT_UNITY_CHECK_FREQUENCIES
+            .iter()
+            .map(f32::to_string)
+            .collect::<Vec<String>>()
+            .join(", ");
+
         let ui_weak = self.ui.clone();
 
         let _ = ui_weak.upgrade_in_event_loop(move |ui| {
@@ -247,10 +673,146 @@ impl UI {
             ui.set_license(SharedString::from(LICENSE.to_string()));
             ui.set_reference_frequency(reference_frequency);
             ui.set_reference_level(reference_level);
+            ui.set_tone_waveform_index(get_selector_index_from_waveform(waveform));
+            ui.set_sweep_mode_active(waveform == Waveform::Sweep);
+            ui.set_delta_mode_active(delta_mode_active);
+            ui.set_unity_tolerance(SharedString::from(unity_tolerance));
+            ui.set_close_tolerance(SharedString::from(close_tolerance));
+            ui.set_analog_unit_index(get_selector_index_from_item(&ANALOG_UNITS, analog_unit));
+            ui.set_meter_display_index(get_selector_index_from_item(&METER_DISPLAYS, meter_display));
+            ui.set_colour_theme_index(get_selector_index_from_item(&COLOUR_THEMES, colour_theme));
+            set_bar_meter_scale_in_ui(&ui, &state);
+            ui.set_left_level(get_level_readout(f32::NEG_INFINITY, &State::default()));
+            ui.set_right_level(get_level_readout(f32::NEG_INFINITY, &State::default()));
+            ui.set_sweep_start_frequency(sweep_settings.start_frequency as i32);
+            ui.set_sweep_stop_frequency(sweep_settings.stop_frequency as i32);
+            ui.set_sweep_duration(sweep_settings.duration_in_seconds as i32);
+            ui.set_sweep_fade_enabled(sweep_settings.fade_enabled);
+            ui.set_sweep_loop_enabled(sweep_settings.loop_enabled);
+            ui.set_distortion_low_frequency_limit(distortion_settings.low_frequency_limit as i32);
+            ui.set_distortion_high_frequency_limit(distortion_settings.high_frequency_limit as i32);
+            ui.set_distortion_a_weighting_enabled(distortion_settings.a_weighting_enabled);
+            ui.set_spectrum_fft_size_index(get_spectrum_fft_size_index(spectrum_settings.fft_size));
+            ui.set_spectrum_window_index(get_spectrum_window_index(spectrum_settings.window_function));
+            ui.set_spectrum_averages(spectrum_settings.averages as i32);
+            ui.set_spectrum_logarithmic_frequency(true);
+            ui.set_spectrum_grid_commands(SharedString::from(spectrum_grid_commands));
+            ui.set_left_clip(get_clip_readout_from_count(ClipCount::default()));
+            ui.set_right_clip(get_clip_readout_from_count(ClipCount::default()));
+            ui.set_left_dc_offset(get_dc_offset_readout(None));
+            ui.set_right_dc_offset(get_dc_offset_readout(None));
+            ui.set_frequency_response_grid_commands(SharedString::from(
+                frequency_response_grid_commands,
+            ));
+            ui.set_export_directory(SharedString::from(export_directory));
+            ui.set_unity_check_frequencies(SharedString::from(unity_check_frequencies));
+
+            if let Some(geometry) = window_geometry {
+                ui.window().set_size(PhysicalSize::new(geometry.width, geometry.height));
+                ui.window().set_position(PhysicalPosition::new(geometry.x, geometry.y));
+            }
         });
         
     }
 
+    fn release_guide_device_when_it_is_the_reference_output(&self) {
+        let ui_weak = self.ui.clone();
+        let output_device_name = self.current_output_device.name.clone();
+        let tone_generator_sender = self.tone_generator_sender.clone();
+
+        let _ = self.ui.upgrade_in_event_loop(move |ui| {
+            if ui.get_guide_device() != output_device_name {
+                return;
+            }
+
+            ui.set_guide_device(SharedString::new());
+            ui.set_guide_status(SharedString::from(GUIDE_SAME_DEVICE_TEXT));
+
+            if let Err(error) = tone_generator_sender.send(EventType::GuideDeviceUpdate(None)) {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn remember_current_devices(&self) {
+        let mut settings = self
+            .settings
+            .lock()
+            .unwrap_or_else(|poisoned| {
+                poisoned.into_inner()
+            });
+        settings.input_device = Some(self.current_input_device.clone());
+        settings.output_device = Some(self.current_output_device.clone());
+        let mut saved_settings = settings.clone();
+        drop(settings);
+
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| {
+                poisoned.into_inner()
+            })
+            .update_settings(&mut saved_settings);
+        write_settings_to_the_settings_file(&saved_settings);
+
+        self.update_calibration_display_data();
+    }
+
+    fn update_calibration(
+        &self,
+        direction: CalibrationDirection,
+        calibration: Option<Calibration>,
+    ) {
+        let device_name = match direction {
+            CalibrationDirection::Input => &self.current_input_device.name,
+            CalibrationDirection::Output => &self.current_output_device.name,
+        };
+
+        self.settings
+            .lock()
+            .unwrap_or_else(|poisoned| {
+                poisoned.into_inner()
+            })
+            .calibrations
+            .set(direction, device_name, calibration);
+
+        self.update_calibration_display_data();
+    }
+
+    fn update_calibration_display_data(&self) {
+        let calibrations = self
+            .settings
+            .lock()
+            .unwrap_or_else(|poisoned| {
+                poisoned.into_inner()
+            })
+            .calibrations
+            .clone();
+
+        let state = {
+            let mut state = self
+                .state
+                .lock()
+                .unwrap_or_else(|poisoned| {
+                    poisoned.into_inner()
+                });
+            state.input_calibration =
+                calibrations.get(CalibrationDirection::Input, &self.current_input_device.name);
+            state.output_calibration =
+                calibrations.get(CalibrationDirection::Output, &self.current_output_device.name);
+            *state
+        };
+
+        let _ = self.ui.upgrade_in_event_loop(move |ui| {
+            ui.set_input_calibration(SharedString::from(format_device_calibration_for_display(
+                state.input_calibration,
+            )));
+            ui.set_output_calibration(SharedString::from(format_device_calibration_for_display(
+                state.output_calibration,
+            )));
+            ui.set_tone_analog_level(SharedString::from(get_tone_analog_level_text(&state)));
+        });
+    }
+
     fn initialize_displayed_input_device_data(&mut self) -> Result<(), Box<dyn Error>> {
         let current_input_device = self.current_input_device.clone();
         let input_device_list = self.input_device_list.clone();
@@ -420,222 +982,1052 @@ impl UI {
         Ok(())
     }
 
-    pub fn create_ui_callbacks(&self) {
-        self.on_close_error_dialog();
-        self.on_close_fatal_error_dialog();
+    fn update_frequency_response_display_data(&self) {
+        let left_points = self.frequency_response.left_points();
+        let right_points = self.frequency_response.right_points();
+        let left_commands =
+            get_path_commands_from_points(&left_points, &FREQUENCY_RESPONSE_GRAPH_SCALE);
+        let right_commands =
+            get_path_commands_from_points(&right_points, &FREQUENCY_RESPONSE_GRAPH_SCALE);
+        let left_deviation = self.frequency_response.get_left_maximum_deviation();
+        let right_deviation = self.frequency_response.get_right_maximum_deviation();
+
+        let _ = self.ui.upgrade_in_event_loop(move |ui| {
+            ui.set_frequency_response_left_commands(SharedString::from(left_commands));
+            ui.set_frequency_response_right_commands(SharedString::from(right_commands));
+            ui.set_frequency_response_left_deviation(SharedString::from(
+                format_deviation_for_display(left_deviation),
+            ));
+            ui.set_frequency_response_right_deviation(SharedString::from(
+                format_deviation_for_display(right_deviation),
+            ));
+            ui.set_frequency_response_left_result(get_check_result_from_deviation(
+                left_deviation,
+                FREQUENCY_RESPONSE_FLATNESS_TOLERANCE,
+            ));
+            ui.set_frequency_response_right_result(get_check_result_from_deviation(
+                right_deviation,
+                FREQUENCY_RESPONSE_FLATNESS_TOLERANCE,
+            ));
+        });
+    }
 
-        self.on_select_new_input_device_callback();
-        self.on_select_new_input_channel_callback();
+    fn export_frequency_response(&self, directory: &str) -> Result<(), LocalError> {
+        let directory = Path::new(directory);
 
-        self.on_select_new_output_device_callback();
-        self.on_select_new_output_channel_callback();
+        write_frd_file(
+            &directory.join(FREQUENCY_RESPONSE_LEFT_FILE_NAME),
+            &self.frequency_response.left_points(),
+        )?;
 
-        self.on_start_button_pressed_callback();
-        self.on_delta_mode_switch_toggled_callback();
+        let right_points = self.frequency_response.right_points();
+        if !right_points.is_empty() {
+            write_frd_file(
+                &directory.join(FREQUENCY_RESPONSE_RIGHT_FILE_NAME),
+                &right_points,
+            )?;
+        }
 
-        self.on_reference_tone_frequency_changed_callback();
+        let message = format!("Exported to {}", directory.display());
+        let _ = self.ui.upgrade_in_event_loop(move |ui| {
+            ui.set_measurement_progress(SharedString::from(message));
+        });
 
-        self.on_reference_tone_level_changed_callback();
+        Ok(())
+    }
 
-        self.on_tone_mode_updated_callback();
+    fn update_gain_drift_display_data(&self) {
+        let left_points = self.gain_drift_log.left_points();
+        let right_points = self.gain_drift_log.right_points();
+        let scale = get_gain_drift_graph_scale(&self.gain_drift_log);
+        let grid_commands = get_gain_drift_grid_commands(&scale);
+        let left_commands = get_path_commands_from_points(&left_points, &scale);
+        let right_commands = get_path_commands_from_points(&right_points, &scale);
+        let elapsed = format_elapsed_time(self.gain_drift_log.get_elapsed_in_seconds());
+        let scale_text = format!("+/- {:.1} dB", scale.maximum_level);
+        let left_drift = self.gain_drift_log.get_left_drift();
+        let right_drift = self.gain_drift_log.get_right_drift();
+        let has_points = !self.gain_drift_log.points.is_empty();
+
+        let _ = self.ui.upgrade_in_event_loop(move |ui| {
+            ui.set_gain_drift_grid_commands(SharedString::from(grid_commands));
+            ui.set_gain_drift_left_commands(SharedString::from(left_commands));
+            ui.set_gain_drift_right_commands(SharedString::from(right_commands));
+            ui.set_gain_drift_elapsed(SharedString::from(elapsed));
+            ui.set_gain_drift_scale(SharedString::from(scale_text));
+            ui.set_gain_drift_left_drift(SharedString::from(format_drift_for_display(left_drift)));
+            ui.set_gain_drift_right_drift(SharedString::from(format_drift_for_display(
+                right_drift,
+            )));
+            ui.set_gain_drift_has_points(has_points);
+        });
     }
 
-    fn on_start_button_pressed_callback(&self) {
-        let ui_weak = self.ui.clone();
-        let ui = if let Some(ui) = ui_weak.upgrade() {
-            ui
-        } else {
-            eprintln!("Start Button Callback: {}", FATAL_ERROR_MESSAGE_UI_ERROR);
-            exit(1);
-        };
+    fn export_gain_drift(&self, directory: &str) -> Result<(), LocalError> {
+        let file_path = Path::new(directory).join(GAIN_DRIFT_FILE_NAME);
+        export_gain_drift_csv_file(&get_gain_drift_log_file_path(), &file_path)?;
 
-        let level_meter_sender = self.level_meter_sender.clone();
-        let tone_generator_sender = self.tone_generator_sender.clone();
+        let message = format!("Exported to {}", file_path.display());
+        let _ = self.ui.upgrade_in_event_loop(move |ui| {
+            ui.set_measurement_progress(SharedString::from(message));
+        });
 
-        ui.on_start_button_pressed(move |is_active| {
-            let event_type = if is_active {
-                EventType::Start
-            } else {
-                EventType::Stop
-            };
+        Ok(())
+    }
 
-            if let Err(error) = level_meter_sender.send(event_type.clone()) {
-                eprintln!("Error sending event: {error}");
-                handle_error_in_ui(&ui_weak, &error.to_string());
-            }
+    fn update_preset_list_display_data(&self, current_preset: Option<String>) {
+        let preset_names = get_presets_directory()
+            .map(|directory| get_preset_names(&directory))
+            .unwrap_or_default();
 
-            if let Err(error) = tone_generator_sender.send(event_type.clone()) {
-                eprintln!("Error sending event: {error}");
-                handle_error_in_ui(&ui_weak, &error.to_string());
+        let _ = self.ui.upgrade_in_event_loop(move |ui| {
+            if let Some(name) = current_preset {
+                ui.set_current_preset(SharedString::from(name));
+            } else if let Some(name) = preset_names.first() {
+                ui.set_current_preset(SharedString::from(name));
             }
+            ui.set_preset_list(get_model_from_string_slice(&preset_names));
         });
     }
 
-    fn on_select_new_input_device_callback(&self) {
-        let ui_weak = self.ui.clone();
-        let ui = if let Some(ui) = ui_weak.upgrade() {
-            ui
-        } else {
-            eprintln!(
-                "New Input Device Callback: {}",
-                FATAL_ERROR_MESSAGE_UI_ERROR
-            );
-            exit(1);
-        };
+    fn update_preset_status(&self, message: String) {
+        let _ = self.ui.upgrade_in_event_loop(move |ui| {
+            ui.set_preset_status(SharedString::from(message));
+        });
+    }
 
-        let user_interface_sender = self.user_interface_sender.clone();
+    fn load_preset(&mut self, name: &str) -> Result<(), Box<dyn Error>> {
+        let directory = get_presets_directory().ok_or(LocalError::PresetDirectory)?;
+        let preset = read_preset_file(&get_preset_file_path(&directory, name)?)?;
 
-        ui.on_selected_input_device(move |device| {
-            let device_name = device.to_string();
+        self.apply_preset(&preset)?;
+        self.update_preset_status(format!("Loaded {name}"));
 
-            if let Err(error) =
-                user_interface_sender.send(EventType::InputDeviceUpdate(device_name.clone()))
-            {
-                handle_error_in_ui(&ui_weak, &error.to_string());
-            }
-        });
+        Ok(())
     }
 
-    fn on_select_new_output_device_callback(&self) {
-        let ui_weak = self.ui.clone();
-        let ui = if let Some(ui) = ui_weak.upgrade() {
-            ui
-        } else {
-            eprintln!("New Output Callback: {}", FATAL_ERROR_MESSAGE_UI_ERROR);
-            exit(1);
+    fn save_preset(&self, name: &str) -> Result<(), LocalError> {
+        let directory = get_presets_directory().ok_or(LocalError::PresetDirectory)?;
+        let state = *self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| {
+                poisoned.into_inner()
+            });
+
+        let mut preset = Settings {
+            input_device: Some(self.current_input_device.clone()),
+            output_device: Some(self.current_output_device.clone()),
+            ..Settings::default()
         };
+        state.update_settings(&mut preset);
 
-        let user_interface_sender = self.user_interface_sender.clone();
+        write_preset_file(&get_preset_file_path(&directory, name)?, &preset)?;
+        self.update_preset_list_display_data(Some(name.trim().to_string()));
+        self.update_preset_status(format!("Saved {}", name.trim()));
 
-        ui.on_selected_output_device(move |device| {
-            if let Err(error) =
-                user_interface_sender.send(EventType::OutputDeviceUpdate(device.to_string()))
-            {
-                handle_error_in_ui(&ui_weak, &error.to_string());
-            }
-        });
+        Ok(())
     }
 
-    fn on_select_new_input_channel_callback(&self) {
-        let ui_weak = self.ui.clone();
-        let ui = if let Some(ui) = ui_weak.upgrade() {
-            ui
-        } else {
-            eprintln!(
-                "New Input Channel Callback: {}",
-                FATAL_ERROR_MESSAGE_UI_ERROR
-            );
-            exit(1);
-        };
+    fn import_preset(&self, file_path: &str) -> Result<(), LocalError> {
+        let directory = get_presets_directory().ok_or(LocalError::PresetDirectory)?;
+        let file_path = Path::new(file_path.trim());
+        let name = file_path
+            .file_stem()
+            .map(|name| name.to_string_lossy().to_string())
+            .ok_or_else(|| LocalError::PresetRead(file_path.display().to_string()))?;
+
+        let preset = read_preset_file(file_path)?;
+        let preset_file_path = get_preset_file_path(&directory, &name)?;
+        write_preset_file(&preset_file_path, &preset)?;
+
+        let imported_name = preset_file_path
+            .file_stem()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or(name);
+        self.update_preset_status(format!("Imported {imported_name}"));
+        self.update_preset_list_display_data(Some(imported_name));
 
-        let user_interface_sender = self.user_interface_sender.clone();
+        Ok(())
+    }
 
-        ui.on_selected_input_channel(move |left_channel, right_channel| {
-            let left_input_channel = left_channel.to_string();
-            let right_input_channel = if right_channel.is_empty() {
-                None
-            } else {
-                Some(right_channel.to_string())
-            };
+    fn export_preset(&self, name: &str, directory: &str) -> Result<(), LocalError> {
+        let presets_directory = get_presets_directory().ok_or(LocalError::PresetDirectory)?;
+        let preset = read_preset_file(&get_preset_file_path(&presets_directory, name)?)?;
 
-            if let Err(error) = user_interface_sender.send(EventType::InputChannelUpdate {
-                left: left_input_channel,
-                right: right_input_channel,
-            }) {
+        let file_path = get_preset_file_path(Path::new(directory), name)?;
+        write_preset_file(&file_path, &preset)?;
+        self.update_preset_status(format!("Exported to {}", file_path.display()));
+
+        Ok(())
+    }
+
+    fn apply_preset(&mut self, preset: &Settings) -> Result<(), Box<dyn Error>> {
+        let state = {
+            let mut state = self
+                .state
+                .lock()
+                .unwrap_or_else(|poisoned| {
+                    poisoned.into_inner()
+                });
+            state.apply_settings(preset);
+            *state
+        };
+
+        self.restore_tone_generator_settings();
+
+        let reference_level = preset.reference_level;
+        for sender in [&self.tone_generator_sender, &self.level_meter_sender] {
+            if let Err(error) = sender.send(EventType::ToneLevelUpdate(reference_level)) {
+                handle_error_in_ui(&self.ui, &error.to_string());
+            }
+        }
+
+        let reference_frequency = preset.reference_frequency;
+        if let Err(error) = self
+            .level_meter_sender
+            .send(EventType::MeterReferenceFrequencyUpdate(reference_frequency))
+        {
+            handle_error_in_ui(&self.ui, &error.to_string());
+        }
+
+        let waveform = preset.waveform;
+        let delta_mode_active = preset.delta_mode_active;
+        let unity_tolerance = format_tolerance_for_display(Some(preset.unity_tolerance));
+        let close_tolerance = format_tolerance_for_display(preset.close_tolerance);
+        let analog_unit = preset.analog_unit;
+        let meter_display = preset.meter_display;
+        self.ui.upgrade_in_event_loop(move |ui| {
+            ui.set_reference_frequency(reference_frequency);
+            ui.set_reference_level(reference_level);
+            ui.set_tone_waveform_index(get_selector_index_from_waveform(waveform));
+            ui.set_sweep_mode_active(waveform == Waveform::Sweep);
+            ui.set_delta_mode_active(delta_mode_active);
+            ui.set_unity_tolerance(SharedString::from(unity_tolerance));
+            ui.set_close_tolerance(SharedString::from(close_tolerance));
+            ui.set_analog_unit_index(get_selector_index_from_item(&ANALOG_UNITS, analog_unit));
+            ui.set_meter_display_index(get_selector_index_from_item(&METER_DISPLAYS, meter_display));
+            set_bar_meter_scale_in_ui(&ui, &state);
+        })?;
+
+        if let Some(input_device) = &preset.input_device {
+            self.select_saved_input_device(input_device)?;
+        }
+
+        if let Some(output_device) = &preset.output_device {
+            self.select_saved_output_device(output_device)?;
+        }
+
+        self.remember_current_devices();
+
+        Ok(())
+    }
+
+    fn select_saved_input_device(&mut self, device: &CurrentDevice) -> Result<(), Box<dyn Error>> {
+        let Some(device_index) = self
+            .input_device_list
+            .devices
+            .iter()
+            .position(|name| *name == device.name)
+        else {
+            handle_error_in_ui(
+                &self.ui,
+                &LocalError::DeviceNameNotPresent(device.name.clone()).to_string(),
+            );
+            return Ok(());
+        };
+
+        self.current_input_device = get_device_with_saved_channels(
+            device.name.clone(),
+            &self.input_device_list.channels[device_index],
+            Some(device),
+        );
+
+        let device_name = self.current_input_device.name.clone();
+        let displayed_device_name = device_name.clone();
+        self.ui.upgrade_in_event_loop(move |ui| {
+            ui.set_current_input_device(SharedString::from(displayed_device_name));
+        })?;
+        self.update_input_device_display_data(&device_name)?;
+
+        if let Err(error) = self.level_meter_sender.send(EventType::MeterDeviceUpdate {
+            name: device_name,
+            left: self.current_input_device.left_channel.clone(),
+            right: self.current_input_device.right_channel.clone(),
+        }) {
+            handle_error_in_ui(&self.ui, &error.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn select_saved_output_device(&mut self, device: &CurrentDevice) -> Result<(), Box<dyn Error>> {
+        let Some(device_index) = self
+            .output_device_list
+            .devices
+            .iter()
+            .position(|name| *name == device.name)
+        else {
+            handle_error_in_ui(
+                &self.ui,
+                &LocalError::DeviceNameNotPresent(device.name.clone()).to_string(),
+            );
+            return Ok(());
+        };
+
+        self.current_output_device = get_device_with_saved_channels(
+            device.name.clone(),
+            &self.output_device_list.channels[device_index],
+            Some(device),
+        );
+
+        let device_name = self.current_output_device.name.clone();
+        let displayed_device_name = device_name.clone();
+        self.ui.upgrade_in_event_loop(move |ui| {
+            ui.set_current_output_device(SharedString::from(displayed_device_name));
+        })?;
+        self.update_output_device_display_data(&device_name)?;
+
+        if let Err(error) = self.tone_generator_sender.send(EventType::ToneDeviceUpdate {
+            name: device_name,
+            left: self.current_output_device.left_channel.clone(),
+            right: self.current_output_device.right_channel.clone(),
+        }) {
+            handle_error_in_ui(&self.ui, &error.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn restore_tone_generator_settings(&self) {
+        let state = *self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| {
+                poisoned.into_inner()
+            });
+
+        if let Err(error) = self
+            .tone_generator_sender
+            .send(EventType::ToneFrequencyUpdate(state.reference_frequency))
+        {
+            handle_error_in_ui(&self.ui, &error.to_string());
+        }
+
+        if let Err(error) = self
+            .tone_generator_sender
+            .send(EventType::ToneWaveformUpdate(state.waveform))
+        {
+            handle_error_in_ui(&self.ui, &error.to_string());
+        }
+
+        if let Err(error) = self
+            .tone_generator_sender
+            .send(EventType::ToneOutputChannelsUpdate(OutputChannels::Both))
+        {
+            handle_error_in_ui(&self.ui, &error.to_string());
+        }
+    }
+
+    pub fn create_ui_callbacks(&self) {
+        self.on_close_error_dialog();
+        self.on_close_fatal_error_dialog();
+
+        self.on_select_new_input_device_callback();
+        self.on_select_new_input_channel_callback();
+
+        self.on_select_new_output_device_callback();
+        self.on_select_new_output_channel_callback();
+        self.on_channel_pair_stepped_callback();
+
+        self.on_start_button_pressed_callback();
+        self.on_delta_mode_switch_toggled_callback();
+        self.on_tolerance_changed_callback();
+        self.on_meter_display_selected_callback();
+        self.on_colour_theme_selected_callback();
+        self.on_fullscreen_toggled_callback();
+
+        self.on_reference_tone_frequency_changed_callback();
+
+        self.on_reference_tone_level_changed_callback();
+
+        self.on_tone_waveform_selected_callback();
+
+        self.on_sweep_settings_changed_callback();
+
+        self.on_distortion_settings_changed_callback();
+        self.on_spectrum_settings_changed_callback();
+        self.on_clip_threshold_changed_callback();
+        self.on_clip_reset_pressed_callback();
+        self.on_dc_blocking_toggled_callback();
+
+        self.on_measurement_cancelled_callback();
+        self.on_frequency_response_started_callback();
+        self.on_frequency_response_exported_callback();
+        self.on_unity_check_started_callback();
+        self.on_noise_floor_started_callback();
+        self.on_crosstalk_started_callback();
+        self.on_latency_started_callback();
+        self.on_polarity_started_callback();
+        self.on_gain_drift_started_callback();
+        self.on_gain_drift_exported_callback();
+
+        self.on_preset_loaded_callback();
+        self.on_preset_saved_callback();
+        self.on_preset_imported_callback();
+        self.on_preset_exported_callback();
+
+        self.on_analog_unit_selected_callback();
+        self.on_input_calibration_changed_callback();
+        self.on_output_calibration_changed_callback();
+
+        self.on_guide_channel_selected_callback();
+        self.on_guide_device_selected_callback();
+
+        self.on_window_close_requested_callback();
+    }
+
+    fn on_start_button_pressed_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Start Button Callback");
+
+        let level_meter_sender = self.level_meter_sender.clone();
+        let tone_generator_sender = self.tone_generator_sender.clone();
+
+        ui.on_start_button_pressed(move |is_active| {
+            let event_type = if is_active {
+                EventType::Start
+            } else {
+                EventType::Stop
+            };
+
+            if let Err(error) = level_meter_sender.send(event_type.clone()) {
+                eprintln!("Error sending event: {error}");
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+
+            if let Err(error) = tone_generator_sender.send(event_type.clone()) {
+                eprintln!("Error sending event: {error}");
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_select_new_input_device_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "New Input Device Callback");
+
+        let user_interface_sender = self.user_interface_sender.clone();
+
+        ui.on_selected_input_device(move |device| {
+            let device_name = device.to_string();
+
+            if let Err(error) =
+                user_interface_sender.send(EventType::InputDeviceUpdate(device_name.clone()))
+            {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_select_new_output_device_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "New Output Callback");
+
+        let user_interface_sender = self.user_interface_sender.clone();
+
+        ui.on_selected_output_device(move |device| {
+            if let Err(error) =
+                user_interface_sender.send(EventType::OutputDeviceUpdate(device.to_string()))
+            {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_select_new_input_channel_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "New Input Channel Callback");
+
+        let user_interface_sender = self.user_interface_sender.clone();
+
+        ui.on_selected_input_channel(move |left_channel, right_channel| {
+            let left_input_channel = left_channel.to_string();
+            let right_input_channel = if right_channel.is_empty() {
+                None
+            } else {
+                Some(right_channel.to_string())
+            };
+
+            if let Err(error) = user_interface_sender.send(EventType::InputChannelUpdate {
+                left: left_input_channel,
+                right: right_input_channel,
+            }) {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_select_new_output_channel_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "New Output Channel Callback");
+
+        let user_interface_sender = self.user_interface_sender.clone();
+
+        ui.on_selected_output_channel(move |left_channel, right_channel| {
+            let left_output_channel = left_channel.to_string();
+            let right_output_channel = if right_channel.is_empty() {
+                None
+            } else {
+                Some(right_channel.to_string())
+            };
+
+            if let Err(error) = user_interface_sender.send(EventType::OutputChannelUpdate {
+                left: left_output_channel,
+                right: right_output_channel,
+            }) {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_channel_pair_stepped_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Channel Pair Stepped Callback");
+
+        ui.on_channel_pair_stepped(move |input, step| {
+            let Some(ui) = ui_weak.upgrade() else {
+                return;
+            };
+
+            let (channel_list, left_channel, stereo) = if input {
+                (
+                    ui.get_input_channel_list(),
+                    ui.get_left_current_input_channel(),
+                    ui.get_right_input_enabled(),
+                )
+            } else {
+                (
+                    ui.get_output_channel_list(),
+                    ui.get_left_current_output_channel(),
+                    ui.get_right_output_enabled(),
+                )
+            };
+            let channels: Vec<String> = channel_list.iter().map(|channel| channel.to_string()).collect();
+
+            let Some((left, right)) = get_stepped_channel_pair(&channels, &left_channel, stereo, step)
+            else {
+                return;
+            };
+            let right = SharedString::from(right.unwrap_or_default());
+
+            if input {
+                ui.invoke_select_input_channels(SharedString::from(left), right);
+            } else {
+                ui.invoke_select_output_channels(SharedString::from(left), right);
+            }
+        });
+    }
+
+    fn on_reference_tone_frequency_changed_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Tone Frequency Change Callback");
+
+        let reference_tone_sender = self.tone_generator_sender.clone();
+        let level_meter_sender = self.level_meter_sender.clone();
+        let state_arc = self.state.clone();
+
+        ui.on_tone_frequency_changed(move |frequency| {
+            state_arc
+                .lock()
+                .unwrap_or_else(|poisoned| {
+                    poisoned.into_inner()
+                })
+                .reference_frequency = frequency;
+
+            if let Err(error) =
+                reference_tone_sender.send(EventType::ToneFrequencyUpdate(frequency))
+            {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+
+            if let Err(error) =
+                level_meter_sender.send(EventType::MeterReferenceFrequencyUpdate(frequency))
+            {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_tone_waveform_selected_callback(&self) {
+        let ui_weak = self.ui.clone();
+
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Tone Waveform Selected Callback");
+
+        let tone_generator_sender = self.tone_generator_sender.clone();
+        let state_arc = self.state.clone();
+
+        ui.on_tone_waveform_selected(move |index| {
+            let waveform = get_waveform_from_selector_index(index);
+
+            state_arc
+                .lock()
+                .unwrap_or_else(|poisoned| {
+                    poisoned.into_inner()
+                })
+                .waveform = waveform;
+
+            if let Some(ui) = ui_weak.upgrade() {
+                ui.set_sweep_mode_active(waveform == Waveform::Sweep);
+                ui.set_sweep_current_frequency(SharedString::from("-"));
+            }
+
+            if let Err(error) = tone_generator_sender.send(EventType::ToneWaveformUpdate(waveform))
+            {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_sweep_settings_changed_callback(&self) {
+        let ui_weak = self.ui.clone();
+
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Sweep Settings Changed Callback");
+
+        let tone_generator_sender = self.tone_generator_sender.clone();
+
+        ui.on_sweep_settings_changed(
+            move |start_frequency, stop_frequency, duration, fade_enabled, loop_enabled| {
+                let sweep_settings = SweepSettings {
+                    start_frequency: start_frequency as f32,
+                    stop_frequency: stop_frequency as f32,
+                    duration_in_seconds: duration as f32,
+                    fade_enabled,
+                    loop_enabled,
+                };
+
+                if let Err(error) =
+                    tone_generator_sender.send(EventType::ToneSweepUpdate(sweep_settings))
+                {
+                    handle_error_in_ui(&ui_weak, &error.to_string());
+                }
+            },
+        );
+    }
+
+    fn on_distortion_settings_changed_callback(&self) {
+        let ui_weak = self.ui.clone();
+
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Distortion Settings Changed Callback");
+
+        let level_meter_sender = self.level_meter_sender.clone();
+
+        ui.on_distortion_settings_changed(
+            move |low_frequency_limit, high_frequency_limit, a_weighting_enabled| {
+                let distortion_settings = DistortionSettings {
+                    low_frequency_limit: low_frequency_limit as f32,
+                    high_frequency_limit: high_frequency_limit as f32,
+                    a_weighting_enabled,
+                };
+
+                if let Err(error) = level_meter_sender
+                    .send(EventType::MeterDistortionSettingsUpdate(distortion_settings))
+                {
+                    handle_error_in_ui(&ui_weak, &error.to_string());
+                }
+            },
+        );
+    }
+
+    fn on_spectrum_settings_changed_callback(&self) {
+        let ui_weak = self.ui.clone();
+
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Spectrum Settings Changed Callback");
+
+        let level_meter_sender = self.level_meter_sender.clone();
+        let state_arc = self.state.clone();
+
+        ui.on_spectrum_settings_changed(
+            move |fft_size_index, window_index, averages, logarithmic_frequency| {
+                state_arc
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .spectrum_logarithmic_frequency = logarithmic_frequency;
+
+                if let Some(ui) = ui_weak.upgrade() {
+                    ui.set_spectrum_grid_commands(SharedString::from(
+                        get_spectrum_grid_commands(logarithmic_frequency),
+                    ));
+                }
+
+                let spectrum_settings =
+                    get_spectrum_settings_from_indexes(fft_size_index, window_index, averages);
+
+                if let Err(error) = level_meter_sender
+                    .send(EventType::MeterSpectrumSettingsUpdate(spectrum_settings))
+                {
+                    handle_error_in_ui(&ui_weak, &error.to_string());
+                }
+            },
+        );
+    }
+
+    fn on_clip_threshold_changed_callback(&self) {
+        let ui_weak = self.ui.clone();
+
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Clip Threshold Changed Callback");
+
+        let level_meter_sender = self.level_meter_sender.clone();
+
+        ui.on_clip_threshold_changed(move |threshold| {
+            let default_settings = ClipSettings::default();
+            let clip_settings = ClipSettings {
+                threshold_dbfs: threshold
+                    .parse::<f32>()
+                    .unwrap_or(default_settings.threshold_dbfs),
+                ..default_settings
+            };
+
+            if let Err(error) =
+                level_meter_sender.send(EventType::MeterClipSettingsUpdate(clip_settings))
+            {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_clip_reset_pressed_callback(&self) {
+        let ui_weak = self.ui.clone();
+
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Clip Reset Callback");
+
+        let level_meter_sender = self.level_meter_sender.clone();
+
+        ui.on_clip_reset_pressed(move || {
+            if let Err(error) = level_meter_sender.send(EventType::MeterClipReset) {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_dc_blocking_toggled_callback(&self) {
+        let ui_weak = self.ui.clone();
+
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "DC Blocking Toggled Callback");
+
+        let level_meter_sender = self.level_meter_sender.clone();
+
+        ui.on_dc_blocking_toggled(move |enabled| {
+            if let Err(error) = level_meter_sender.send(EventType::MeterDcBlockingUpdate(enabled)) {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_measurement_cancelled_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Measurement Cancelled Callback");
+
+        let measurement_sender = self.measurement_sender.clone();
+
+        ui.on_measurement_cancelled(move || {
+            if let Err(error) = measurement_sender.send(EventType::MeasurementStop) {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_frequency_response_started_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Frequency Response Started Callback");
+
+        let measurement_sender = self.measurement_sender.clone();
+        let state_arc = self.state.clone();
+
+        ui.on_frequency_response_started(move |start_frequency, stop_frequency, points_per_octave| {
+            let state = *state_arc
+                .lock()
+                .unwrap_or_else(|poisoned| {
+                    poisoned.into_inner()
+                });
+
+            let settings = FrequencyResponseSettings {
+                start_frequency: start_frequency as f32,
+                stop_frequency: stop_frequency as f32,
+                points_per_octave: points_per_octave.max(1) as u32,
+                reference_frequency: state.reference_frequency,
+            };
+
+            if let Err(error) = measurement_sender.send(EventType::MeasurementStart(
+                MeasurementRequest::FrequencyResponse(settings),
+            )) {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_frequency_response_exported_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Frequency Response Exported Callback");
+
+        let user_interface_sender = self.user_interface_sender.clone();
+
+        ui.on_frequency_response_exported(move |directory| {
+            if let Err(error) = user_interface_sender
+                .send(EventType::FrequencyResponseExport(directory.to_string()))
+            {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_unity_check_started_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Unity Check Started Callback");
+
+        let measurement_sender = self.measurement_sender.clone();
+        let state_arc = self.state.clone();
+
+        ui.on_unity_check_started(move |frequency_list, tolerance| {
+            let frequencies = match get_frequencies_from_text(frequency_list.as_str()) {
+                Ok(frequencies) => frequencies,
+                Err(error) => {
+                    handle_error_in_ui(&ui_weak, &error.to_string());
+                    return;
+                }
+            };
+
+            let tolerance = match get_tolerance_from_text(tolerance.as_str()) {
+                Ok(tolerance) => tolerance,
+                Err(error) => {
+                    handle_error_in_ui(&ui_weak, &error.to_string());
+                    return;
+                }
+            };
+
+            let state = *state_arc
+                .lock()
+                .unwrap_or_else(|poisoned| {
+                    poisoned.into_inner()
+                });
+
+            let settings = UnityCheckSettings {
+                frequencies,
+                reference_level: state.reference_level,
+                tolerance,
+            };
+
+            if let Err(error) = measurement_sender.send(EventType::MeasurementStart(
+                MeasurementRequest::UnityCheck(settings),
+            )) {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_noise_floor_started_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Noise Floor Started Callback");
+
+        let measurement_sender = self.measurement_sender.clone();
+        let state_arc = self.state.clone();
+
+        ui.on_noise_floor_started(move || {
+            let state = *state_arc
+                .lock()
+                .unwrap_or_else(|poisoned| {
+                    poisoned.into_inner()
+                });
+
+            let settings = NoiseFloorSettings {
+                reference_frequency: state.reference_frequency,
+            };
+
+            if let Err(error) = measurement_sender.send(EventType::MeasurementStart(
+                MeasurementRequest::NoiseFloor(settings),
+            )) {
                 handle_error_in_ui(&ui_weak, &error.to_string());
             }
         });
     }
 
-    fn on_select_new_output_channel_callback(&self) {
+    fn on_crosstalk_started_callback(&self) {
         let ui_weak = self.ui.clone();
-        let ui = if let Some(ui) = ui_weak.upgrade() {
-            ui
-        } else {
-            eprintln!(
-                "New Output Channel Callback: {}",
-                FATAL_ERROR_MESSAGE_UI_ERROR
-            );
-            exit(1);
-        };
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Crosstalk Started Callback");
 
-        let user_interface_sender = self.user_interface_sender.clone();
+        let measurement_sender = self.measurement_sender.clone();
+        let state_arc = self.state.clone();
 
-        ui.on_selected_output_channel(move |left_channel, right_channel| {
-            let left_output_channel = left_channel.to_string();
-            let right_output_channel = if right_channel.is_empty() {
-                None
-            } else {
-                Some(right_channel.to_string())
+        ui.on_crosstalk_started(move || {
+            let state = *state_arc
+                .lock()
+                .unwrap_or_else(|poisoned| {
+                    poisoned.into_inner()
+                });
+
+            let settings = CrosstalkSettings {
+                reference_frequency: state.reference_frequency,
             };
 
-            if let Err(error) = user_interface_sender.send(EventType::OutputChannelUpdate {
-                left: left_output_channel,
-                right: right_output_channel,
-            }) {
+            if let Err(error) = measurement_sender.send(EventType::MeasurementStart(
+                MeasurementRequest::Crosstalk(settings),
+            )) {
                 handle_error_in_ui(&ui_weak, &error.to_string());
             }
         });
     }
 
-    fn on_reference_tone_frequency_changed_callback(&self) {
+    fn on_latency_started_callback(&self) {
         let ui_weak = self.ui.clone();
-        let ui = if let Some(ui) = ui_weak.upgrade() {
-            ui
-        } else {
-            eprintln!(
-                "Tone Frequency Change Callback: {}",
-                FATAL_ERROR_MESSAGE_UI_ERROR
-            );
-            exit(1);
-        };
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Latency Started Callback");
 
-        let reference_tone_sender = self.tone_generator_sender.clone();
+        let measurement_sender = self.measurement_sender.clone();
 
-        ui.on_tone_frequency_changed(move |frequency| {
+        ui.on_latency_started(move |number_of_bursts| {
+            let settings = LatencySettings {
+                number_of_bursts: number_of_bursts.max(1) as usize,
+            };
+
+            if let Err(error) = measurement_sender.send(EventType::MeasurementStart(
+                MeasurementRequest::Latency(settings),
+            )) {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_polarity_started_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Polarity Started Callback");
+
+        let measurement_sender = self.measurement_sender.clone();
+        let state_arc = self.state.clone();
+
+        ui.on_polarity_started(move || {
+            let state = *state_arc
+                .lock()
+                .unwrap_or_else(|poisoned| {
+                    poisoned.into_inner()
+                });
+
+            let settings = PolaritySettings {
+                reference_frequency: state.reference_frequency,
+            };
+
+            if let Err(error) = measurement_sender.send(EventType::MeasurementStart(
+                MeasurementRequest::Polarity(settings),
+            )) {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_gain_drift_started_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Gain Drift Started Callback");
+
+        let measurement_sender = self.measurement_sender.clone();
+        let state_arc = self.state.clone();
+
+        ui.on_gain_drift_started(move |interval_in_seconds| {
+            let state = *state_arc
+                .lock()
+                .unwrap_or_else(|poisoned| {
+                    poisoned.into_inner()
+                });
+
+            let settings = GainDriftSettings {
+                reference_frequency: state.reference_frequency,
+                reference_level: state.reference_level,
+                interval_in_seconds: interval_in_seconds.max(1) as u32,
+                log_file_path: get_gain_drift_log_file_path(),
+            };
+
+            if let Err(error) = measurement_sender.send(EventType::MeasurementStart(
+                MeasurementRequest::GainDrift(settings),
+            )) {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_gain_drift_exported_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Gain Drift Exported Callback");
+
+        let user_interface_sender = self.user_interface_sender.clone();
+
+        ui.on_gain_drift_exported(move |directory| {
             if let Err(error) =
-                reference_tone_sender.send(EventType::ToneFrequencyUpdate(frequency))
+                user_interface_sender.send(EventType::GainDriftExport(directory.to_string()))
             {
                 handle_error_in_ui(&ui_weak, &error.to_string());
             }
         });
     }
 
-    fn on_tone_mode_updated_callback(&self) {
+    fn on_preset_loaded_callback(&self) {
         let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Preset Loaded Callback");
 
-        let ui = if let Some(ui) = ui_weak.upgrade() {
-            ui
-        } else {
-            eprintln!(
-                "Tone Mode Updated Callback: {}",
-                FATAL_ERROR_MESSAGE_UI_ERROR
-            );
-            exit(1);
-        };
+        let user_interface_sender = self.user_interface_sender.clone();
 
-        let tone_generator_sender = self.tone_generator_sender.clone();
+        ui.on_preset_loaded(move |name| {
+            if let Err(error) = user_interface_sender.send(EventType::PresetLoad(name.to_string()))
+            {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
 
-        ui.on_tone_mode_checked(move |sine_mode_enabled| {
+    fn on_preset_saved_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Preset Saved Callback");
+
+        let user_interface_sender = self.user_interface_sender.clone();
+
+        ui.on_preset_saved(move |name| {
+            if let Err(error) = user_interface_sender.send(EventType::PresetSave(name.to_string()))
+            {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_preset_imported_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Preset Imported Callback");
+
+        let user_interface_sender = self.user_interface_sender.clone();
+
+        ui.on_preset_imported(move |file_path| {
             if let Err(error) =
-                tone_generator_sender.send(EventType::ToneModeUpdate(sine_mode_enabled))
+                user_interface_sender.send(EventType::PresetImport(file_path.to_string()))
             {
                 handle_error_in_ui(&ui_weak, &error.to_string());
             }
         });
     }
 
+    fn on_preset_exported_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Preset Exported Callback");
+
+        let user_interface_sender = self.user_interface_sender.clone();
+
+        ui.on_preset_exported(move |name, directory| {
+            if let Err(error) = user_interface_sender.send(EventType::PresetExport {
+                name: name.to_string(),
+                directory: directory.to_string(),
+            }) {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
     fn on_reference_tone_level_changed_callback(&self) {
         let ui_weak = self.ui.clone();
-        let ui = if let Some(ui) = ui_weak.upgrade() {
-            ui
-        } else {
-            eprintln!(
-                "Tone Level Change Callback: {}",
-                FATAL_ERROR_MESSAGE_UI_ERROR
-            );
-            exit(1);
-        };
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Tone Level Change Callback");
 
         let level_meter_sender = self.level_meter_sender.clone();
         let tone_generator_sender = self.tone_generator_sender.clone();
@@ -650,10 +2042,15 @@ impl UI {
 
             state.reference_level = level;
 
-            if let Err(error) = level_meter_sender.send(EventType::ToneLevelUpdate(level as f32)) {
+            if let Some(ui) = ui_weak.upgrade() {
+                ui.set_tone_analog_level(SharedString::from(get_tone_analog_level_text(&state)));
+                set_bar_meter_scale_in_ui(&ui, &state);
+            }
+
+            if let Err(error) = level_meter_sender.send(EventType::ToneLevelUpdate(level)) {
                 handle_error_in_ui(&ui_weak, &error.to_string());
             }
-            if let Err(error) = tone_generator_sender.send(EventType::ToneLevelUpdate(level as f32))
+            if let Err(error) = tone_generator_sender.send(EventType::ToneLevelUpdate(level))
             {
                 handle_error_in_ui(&ui_weak, &error.to_string());
             }
@@ -662,15 +2059,7 @@ impl UI {
 
     pub fn on_delta_mode_switch_toggled_callback(&self) {
         let ui_weak = self.ui.clone();
-        let ui = if let Some(ui) = ui_weak.upgrade() {
-            ui
-        } else {
-            eprintln!(
-                "Delta Mode Toggled Callback: {}",
-                FATAL_ERROR_MESSAGE_UI_ERROR
-            );
-            exit(1);
-        };
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Delta Mode Toggled Callback");
 
         let state_arc = self.state.clone();
 
@@ -681,12 +2070,236 @@ impl UI {
                     poisoned.into_inner()
                 });
             state.meter_delta_mode_active = delta_mode_active;
+
+            if let Some(ui) = ui_weak.upgrade() {
+                set_bar_meter_scale_in_ui(&ui, &state);
+            }
+        });
+    }
+
+    fn on_tolerance_changed_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Tolerance Changed Callback");
+
+        let state_arc = self.state.clone();
+
+        ui.on_tolerance_changed(move |unity_tolerance, close_tolerance| {
+            let mut state = state_arc
+                .lock()
+                .unwrap_or_else(|poisoned| {
+                    poisoned.into_inner()
+                });
+            state.unity_tolerance = get_tolerance_from_selection(&unity_tolerance)
+                .unwrap_or(DEFAULT_UNITY_TOLERANCE);
+            state.close_tolerance = get_tolerance_from_selection(&close_tolerance);
+
+            if let Some(ui) = ui_weak.upgrade() {
+                set_bar_meter_scale_in_ui(&ui, &state);
+            }
+        });
+    }
+
+    fn on_meter_display_selected_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Meter Display Selected Callback");
+
+        let state_arc = self.state.clone();
+
+        ui.on_meter_display_selected(move |index| {
+            state_arc
+                .lock()
+                .unwrap_or_else(|poisoned| {
+                    poisoned.into_inner()
+                })
+                .meter_display = get_item_from_selector_index(&METER_DISPLAYS, index);
+        });
+    }
+
+    fn on_colour_theme_selected_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Colour Theme Selected Callback");
+
+        let settings_arc = self.settings.clone();
+
+        ui.on_colour_theme_selected(move |index| {
+            settings_arc
+                .lock()
+                .unwrap_or_else(|poisoned| {
+                    poisoned.into_inner()
+                })
+                .colour_theme = get_item_from_selector_index(&COLOUR_THEMES, index);
+        });
+    }
+
+    fn on_fullscreen_toggled_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Fullscreen Toggled Callback");
+
+        let keep_awake: RefCell<Option<KeepAwake>> = RefCell::new(None);
+
+        ui.on_fullscreen_toggled(move |active| {
+            if let Some(ui) = ui_weak.upgrade() {
+                ui.window().set_fullscreen(active);
+            }
+
+            let mut keep_awake = keep_awake.borrow_mut();
+            if !active {
+                *keep_awake = None;
+            } else if keep_awake.is_none() {
+                *keep_awake = KeepAwake::start()
+                    .inspect_err(|error| handle_local_error(error, ""))
+                    .ok();
+            }
+        });
+    }
+
+    fn on_analog_unit_selected_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Analog Unit Selected Callback");
+
+        let state_arc = self.state.clone();
+
+        ui.on_analog_unit_selected(move |index| {
+            let mut state = state_arc
+                .lock()
+                .unwrap_or_else(|poisoned| {
+                    poisoned.into_inner()
+                });
+            state.analog_unit = get_item_from_selector_index(&ANALOG_UNITS, index);
+
+            if let Some(ui) = ui_weak.upgrade() {
+                ui.set_tone_analog_level(SharedString::from(get_tone_analog_level_text(&state)));
+            }
+        });
+    }
+
+    fn on_input_calibration_changed_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Input Calibration Changed Callback");
+
+        let user_interface_sender = self.user_interface_sender.clone();
+
+        ui.on_input_calibration_changed(move |dbfs, dbu| {
+            if let Err(error) = user_interface_sender.send(EventType::CalibrationUpdate {
+                direction: CalibrationDirection::Input,
+                calibration: get_calibration_from_text(&dbfs, &dbu),
+            }) {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_output_calibration_changed_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Output Calibration Changed Callback");
+
+        let user_interface_sender = self.user_interface_sender.clone();
+
+        ui.on_output_calibration_changed(move |dbfs, dbu| {
+            if let Err(error) = user_interface_sender.send(EventType::CalibrationUpdate {
+                direction: CalibrationDirection::Output,
+                calibration: get_calibration_from_text(&dbfs, &dbu),
+            }) {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_guide_channel_selected_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Guide Channel Selected Callback");
+
+        let state_arc = self.state.clone();
+        let tone_generator_sender = self.tone_generator_sender.clone();
+
+        ui.on_guide_channel_selected(move |index| {
+            let mut state = state_arc
+                .lock()
+                .unwrap_or_else(|poisoned| {
+                    poisoned.into_inner()
+                });
+            state.guide_channel = get_item_from_selector_index(&GUIDE_CHANNELS, index);
+
+            if state.guide_channel == GuideChannel::Off
+                && let Err(error) = tone_generator_sender.send(EventType::GuideLevelUpdate {
+                    delta: None,
+                    unity_tolerance: state.unity_tolerance,
+                })
+            {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_guide_device_selected_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Guide Device Selected Callback");
+
+        let tone_generator_sender = self.tone_generator_sender.clone();
+
+        ui.on_guide_device_selected(move |device_name| {
+            let Some(ui) = ui_weak.upgrade() else {
+                return;
+            };
+
+            let is_reference_output = device_name == ui.get_current_output_device();
+            let guide_device = (!is_reference_output).then(|| device_name.to_string());
+
+            if is_reference_output {
+                ui.set_guide_device(SharedString::new());
+                ui.set_guide_status(SharedString::from(GUIDE_SAME_DEVICE_TEXT));
+            } else {
+                ui.set_guide_status(SharedString::new());
+            }
+
+            if let Err(error) = tone_generator_sender.send(EventType::GuideDeviceUpdate(guide_device)) {
+                handle_error_in_ui(&ui_weak, &error.to_string());
+            }
+        });
+    }
+
+    fn on_window_close_requested_callback(&self) {
+        let ui_weak = self.ui.clone();
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Window Close Requested Callback");
+
+        let state_arc = self.state.clone();
+        let settings_arc = self.settings.clone();
+
+        ui.window().on_close_requested(move || {
+            let state = *state_arc
+                .lock()
+                .unwrap_or_else(|poisoned| {
+                    poisoned.into_inner()
+                });
+            let mut settings = settings_arc
+                .lock()
+                .unwrap_or_else(|poisoned| {
+                    poisoned.into_inner()
+                })
+                .clone();
+
+            state.update_settings(&mut settings);
+
+            if let Some(ui) = ui_weak.upgrade() {
+                let position = ui.window().position();
+                let size = ui.window().size();
+                settings.window_geometry = Some(WindowGeometry {
+                    x: position.x,
+                    y: position.y,
+                    width: size.width,
+                    height: size.height,
+                });
+            }
+
+            write_settings_to_the_settings_file(&settings);
+
+            CloseRequestResponse::HideWindow
         });
     }
 
     fn on_close_error_dialog(&self) {
         let ui_weak = self.ui.clone();
-        let ui = get_ui_from_ui_weak_reference(&ui_weak);
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Close Error Dialog Callback");
 
         ui.on_close_error_dialog(move || {
             let _ = ui_weak.upgrade_in_event_loop(|ui| {
@@ -697,7 +2310,7 @@ impl UI {
 
     fn on_close_fatal_error_dialog(&self) {
         let ui_weak = self.ui.clone();
-        let ui = get_ui_from_ui_weak_reference(&ui_weak);
+        let ui = get_ui_from_ui_weak_reference(&ui_weak, "Close Fatal Error Dialog Callback");
 
         ui.on_close_error_dialog(|| {
             exit(EXIT_CODE_ERROR);
@@ -723,15 +2336,23 @@ impl UI {
     }
 }
 
-fn get_ui_from_ui_weak_reference(ui_weak: &Weak<AppWindow>) -> AppWindow {
+fn get_ui_from_ui_weak_reference(ui_weak: &Weak<AppWindow>, callback_name: &str) -> AppWindow {
     if let Some(ui) = ui_weak.upgrade() {
         ui
     } else {
-        eprintln!("Close Dialog Callback: {}", FATAL_ERROR_MESSAGE_UI_ERROR);
+        eprintln!("{callback_name}: {}", FATAL_ERROR_MESSAGE_UI_ERROR);
         exit(1);
     }
 }
 
+fn write_settings_to_the_settings_file(settings: &Settings) {
+    if let Some(file_path) = get_settings_file_path()
+        && let Err(error) = write_settings_file(&file_path, settings)
+    {
+        handle_local_error(&error, "");
+    }
+}
+
 fn get_current_device_index_from_device_list(
     device_list: &DeviceList,
     device_name: &str,
@@ -768,15 +2389,429 @@ fn handle_fatal_error_in_ui(ui_weak: &Weak<AppWindow>, error_message: &str) {
     });
 }
 
+fn get_gain_drift_log_file_path() -> PathBuf {
+    get_settings_file_path()
+        .and_then(|file_path| Some(file_path.parent()?.join(GAIN_DRIFT_LOG_FILE_NAME)))
+        .unwrap_or_else(|| env::temp_dir().join(GAIN_DRIFT_LOG_FILE_NAME))
+}
+
+fn get_default_export_directory() -> String {
+    env::var("HOME")
+        .or_else(|_| env::var("USERPROFILE"))
+        .unwrap_or_else(|_| ".".to_string())
+}
+
+fn get_waveform_from_selector_index(index: i32) -> Waveform {
+    match index {
+        1 => Waveform::Square,
+        2 => Waveform::Sweep,
+        _ => Waveform::Sine,
+    }
+}
+
+fn get_selector_index_from_waveform(waveform: Waveform) -> i32 {
+    match waveform {
+        Waveform::Square => 1,
+        Waveform::Sweep => 2,
+        Waveform::Sine | Waveform::Burst => 0,
+    }
+}
+
+fn format_sweep_frequency_for_display(frequency: f32) -> String {
+    if frequency >= 1000.0 {
+        format!("{:.2}k", frequency / 1000.0)
+    } else {
+        format!("{:.0}", frequency)
+    }
+}
+
+fn get_distortion_readout_from_measurement(
+    measurement: Option<DistortionMeasurement>,
+) -> DistortionReadout {
+    match measurement {
+        Some(measurement) => DistortionReadout {
+            fundamental: SharedString::from(format!("{:.1}", measurement.fundamental_frequency)),
+            thd_n_percent: SharedString::from(format!("{:.4}", measurement.thd_n_percent)),
+            thd_n_db: SharedString::from(format!("{:.1}", measurement.thd_n_db)),
+            sinad: SharedString::from(format!("{:.1}", measurement.sinad_db)),
+        },
+        None => DistortionReadout {
+            fundamental: SharedString::from("-"),
+            thd_n_percent: SharedString::from("-"),
+            thd_n_db: SharedString::from("-"),
+            sinad: SharedString::from("-"),
+        },
+    }
+}
+
+fn get_spectrum_graph_scale(logarithmic_frequency: bool) -> GraphScale {
+    GraphScale {
+        minimum_level: SPECTRUM_MINIMUM_LEVEL,
+        maximum_level: SPECTRUM_MAXIMUM_LEVEL,
+        logarithmic_frequency,
+        ..FREQUENCY_RESPONSE_GRAPH_SCALE
+    }
+}
+
+fn get_spectrum_grid_commands(logarithmic_frequency: bool) -> String {
+    let frequency_lines = if logarithmic_frequency {
+        SPECTRUM_LOGARITHMIC_GRID_FREQUENCIES
+    } else {
+        SPECTRUM_LINEAR_GRID_FREQUENCIES
+    };
+
+    get_grid_path_commands(
+        &get_spectrum_graph_scale(logarithmic_frequency),
+        &frequency_lines,
+        &SPECTRUM_GRID_LEVELS,
+    )
+}
+
+fn get_spectrum_path_commands(spectrum: &Spectrum, scale: &GraphScale) -> (String, String) {
+    let get_commands = |levels: &[f32]| {
+        let points = levels
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(bin, level)| (bin as f32 * spectrum.bin_width, *level))
+            .collect::<Vec<(f32, f32)>>();
+        get_path_commands_from_points(&get_peak_points_per_graph_column(&points, scale), scale)
+    };
+
+    (get_commands(&spectrum.left), get_commands(&spectrum.right))
+}
+
+fn get_spectrum_fft_size_index(fft_size: usize) -> i32 {
+    SPECTRUM_FFT_SIZES
+        .iter()
+        .position(|size| *size == fft_size)
+        .unwrap_or_default() as i32
+}
+
+fn get_spectrum_window_index(window_function: WindowFunction) -> i32 {
+    SPECTRUM_WINDOW_FUNCTIONS
+        .iter()
+        .position(|window| *window == window_function)
+        .unwrap_or_default() as i32
+}
+
+fn get_spectrum_settings_from_indexes(
+    fft_size_index: i32,
+    window_index: i32,
+    averages: i32,
+) -> SpectrumSettings {
+    let default_settings = SpectrumSettings::default();
+
+    SpectrumSettings {
+        fft_size: usize::try_from(fft_size_index)
+            .ok()
+            .and_then(|index| SPECTRUM_FFT_SIZES.get(index).copied())
+            .unwrap_or(default_settings.fft_size),
+        window_function: usize::try_from(window_index)
+            .ok()
+            .and_then(|index| SPECTRUM_WINDOW_FUNCTIONS.get(index).copied())
+            .unwrap_or(default_settings.window_function),
+        averages: averages.max(1) as usize,
+    }
+}
+
+fn get_dc_offset_readout(dc_offset: Option<f32>) -> DcOffsetReadout {
+    let Some(dc_offset) = dc_offset else {
+        return DcOffsetReadout {
+            dbfs: SharedString::from("-"),
+            percent: SharedString::from("-"),
+        };
+    };
+
+    DcOffsetReadout {
+        dbfs: SharedString::from(format_level_value_for_display(Some(get_dbfs_from_amplitude(
+            dc_offset,
+        )))),
+        percent: SharedString::from(format!("{:+.3}", dc_offset * 100.0)),
+    }
+}
+
+fn get_clip_readout_from_count(count: ClipCount) -> ClipReadout {
+    ClipReadout {
+        clipped: count.is_clipped(),
+        overs: SharedString::from(count.overs.to_string()),
+        full_scale_samples: SharedString::from(count.full_scale_samples.to_string()),
+    }
+}
+
+fn get_hum_warning_text(
+    left: Option<HumMeasurement>,
+    right: Option<HumMeasurement>,
+) -> Option<String> {
+    let left = left.filter(HumMeasurement::is_above_warning_threshold);
+    let right = right.filter(HumMeasurement::is_above_warning_threshold);
+
+    let (channels, hum) = match (left, right) {
+        (Some(left), Some(right)) if right.relative_level_db > left.relative_level_db => {
+            (HUM_WARNING_BOTH_CHANNELS_TEXT, right)
+        }
+        (Some(left), Some(_)) => (HUM_WARNING_BOTH_CHANNELS_TEXT, left),
+        (Some(left), None) => (HUM_WARNING_LEFT_CHANNEL_TEXT, left),
+        (None, Some(right)) => (HUM_WARNING_RIGHT_CHANNEL_TEXT, right),
+        (None, None) => return None,
+    };
+
+    Some(format!(
+        "{:.0} Hz mains hum at {:.1} dB relative to the reference tone on the {channels}. {HUM_WARNING_ADVICE_TEXT}",
+        hum.mains_frequency, hum.relative_level_db
+    ))
+}
+
+fn get_input_frequency_readout(frequency: Option<f32>, state: &State) -> InputFrequencyReadout {
+    let Some(frequency) = frequency else {
+        return InputFrequencyReadout {
+            text: SharedString::from("-"),
+            mismatch: false,
+        };
+    };
+
+    let is_steady_tone = matches!(state.waveform, Waveform::Sine | Waveform::Square);
+
+    InputFrequencyReadout {
+        text: SharedString::from(format!("{:.1}", frequency)),
+        mismatch: is_steady_tone
+            && !state.measurement_running
+            && !is_frequency_matching_reference(frequency, state.reference_frequency),
+    }
+}
+
+fn get_stereo_image_readout_from_measurement(
+    stereo_image: Option<StereoImage>,
+) -> StereoImageReadout {
+    match stereo_image {
+        Some(stereo_image) => StereoImageReadout {
+            active: true,
+            correlation: stereo_image.correlation,
+            balance: stereo_image.balance_db,
+            correlation_text: SharedString::from(format!("{:+.2}", stereo_image.correlation)),
+            balance_text: SharedString::from(format!("{:+.1}", stereo_image.balance_db)),
+        },
+        None => StereoImageReadout {
+            active: false,
+            correlation: 0.0,
+            balance: 0.0,
+            correlation_text: SharedString::from("-"),
+            balance_text: SharedString::from("-"),
+        },
+    }
+}
+
+fn format_level_value_for_display(value: Option<f32>) -> String {
+    match value {
+        Some(value) if value.is_finite() => format!("{:.1}", value),
+        _ => "-".to_string(),
+    }
+}
+
 fn format_peak_delta_values_for_display(peak_delta_value: f32) -> String {
-    if peak_delta_value.is_infinite() || peak_delta_value.is_nan() {
-        "-".to_string()
-    } else if (peak_delta_value < 0.0) & (peak_delta_value > -0.1) {
-        "0.0".to_string()
-    } else if peak_delta_value > 0.1 {
-        format!("+{:.1}", peak_delta_value)
+    format_delta_value_for_display(peak_delta_value, 1)
+}
+
+fn format_delta_value_for_display(value: f32, decimal_places: usize) -> String {
+    if value.is_infinite() || value.is_nan() {
+        return "-".to_string();
+    }
+
+    let rounded = get_value_rounded_to_decimal_places(value, decimal_places);
+    if rounded == 0.0 {
+        format!("{:.*}", decimal_places, 0.0)
+    } else if rounded > 0.0 {
+        format!("+{:.*}", decimal_places, rounded)
+    } else {
+        format!("{:.*}", decimal_places, rounded)
+    }
+}
+
+fn get_value_rounded_to_decimal_places(value: f32, decimal_places: usize) -> f32 {
+    let scale = 10.0_f32.powi(decimal_places as i32);
+    (value * scale).round() / scale
+}
+
+fn get_level_decimal_places(unity_tolerance: f32) -> usize {
+    if unity_tolerance < 0.1 - LEVEL_STATUS_ROUNDING_MARGIN {
+        2
+    } else {
+        1
+    }
+}
+
+fn get_level_status_from_delta(
+    delta: f32,
+    unity_tolerance: f32,
+    close_tolerance: Option<f32>,
+) -> LevelStatus {
+    let distance = delta.abs() - LEVEL_STATUS_ROUNDING_MARGIN;
+
+    if distance <= unity_tolerance {
+        LevelStatus::Unity
+    } else if close_tolerance.is_some_and(|close_tolerance| distance <= close_tolerance) {
+        LevelStatus::Close
+    } else if delta < 0.0 {
+        LevelStatus::Low
+    } else {
+        LevelStatus::High
+    }
+}
+
+fn get_level_readout(level: f32, state: &State) -> LevelReadout {
+    if !state.meter_delta_mode_active || !level.is_finite() {
+        let (text, unit) =
+            format_analog_level_for_display(level, state.input_calibration, state.analog_unit)
+                .filter(|_| !state.meter_delta_mode_active)
+                .unwrap_or_else(|| (format_peak_delta_values_for_display(level), ""));
+
+        return LevelReadout {
+            text: SharedString::from(text),
+            unit: SharedString::from(unit),
+            status: LevelStatus::Neutral,
+        };
+    }
+
+    let decimal_places = get_level_decimal_places(state.unity_tolerance);
+    let rounded = get_value_rounded_to_decimal_places(level, decimal_places);
+
+    LevelReadout {
+        text: SharedString::from(format_delta_value_for_display(level, decimal_places)),
+        unit: SharedString::default(),
+        status: get_level_status_from_delta(rounded, state.unity_tolerance, state.close_tolerance),
+    }
+}
+
+fn get_secondary_level_text(level: f32, state: &State) -> String {
+    if state.meter_delta_mode_active {
+        format!(
+            "{SECONDARY_PEAK_LABEL} {} dBFS",
+            format_level_value_for_display(Some(level))
+        )
     } else {
-        format!("{:.1}", peak_delta_value)
+        format!(
+            "{SECONDARY_DELTA_LABEL} {} dB",
+            format_peak_delta_values_for_display(level - state.reference_level)
+        )
+    }
+}
+
+fn get_bar_meter_readout(level: f32, peak: f32, status: LevelStatus, state: &State) -> BarMeterReadout {
+    let range = get_bar_meter_range(state.meter_delta_mode_active);
+
+    BarMeterReadout {
+        level: range.get_position_from_level(level),
+        peak: range.get_position_from_level(peak),
+        status,
+    }
+}
+
+fn get_bar_meter_scale(state: &State) -> BarMeterScale {
+    let range = get_bar_meter_range(state.meter_delta_mode_active);
+    let reference = if state.meter_delta_mode_active {
+        0.0
+    } else {
+        state.reference_level
+    };
+    let close_tolerance = state.close_tolerance.unwrap_or_default();
+
+    BarMeterScale {
+        reference: range.get_position_from_level(reference),
+        unity_low: range.get_position_from_level(reference - state.unity_tolerance),
+        unity_high: range.get_position_from_level(reference + state.unity_tolerance),
+        close_low: range.get_position_from_level(reference - close_tolerance),
+        close_high: range.get_position_from_level(reference + close_tolerance),
+    }
+}
+
+fn get_bar_meter_ticks(state: &State) -> Vec<BarMeterTick> {
+    get_bar_meter_range(state.meter_delta_mode_active)
+        .get_ticks()
+        .into_iter()
+        .map(|(position, level)| BarMeterTick {
+            position,
+            label: SharedString::from(if state.meter_delta_mode_active {
+                format_delta_value_for_display(level, 0)
+            } else {
+                format!("{level:.0}")
+            }),
+        })
+        .collect()
+}
+
+fn set_bar_meter_scale_in_ui(ui: &AppWindow, state: &State) {
+    ui.set_bar_meter_scale(get_bar_meter_scale(state));
+    ui.set_bar_meter_ticks(ModelRc::new(VecModel::from(get_bar_meter_ticks(state))));
+}
+
+fn get_item_from_selector_index<T: Copy + Default>(items: &[T], index: i32) -> T {
+    usize::try_from(index)
+        .ok()
+        .and_then(|index| items.get(index).copied())
+        .unwrap_or_default()
+}
+
+fn get_selector_index_from_item<T: PartialEq>(items: &[T], item: T) -> i32 {
+    items
+        .iter()
+        .position(|candidate| *candidate == item)
+        .unwrap_or_default() as i32
+}
+
+fn get_stepped_channel_pair(
+    channels: &[String],
+    left_channel: &str,
+    stereo: bool,
+    step: i32,
+) -> Option<(String, Option<String>)> {
+    let pair_size = if stereo { 2 } else { 1 };
+    let current_index = channels.iter().position(|channel| channel == left_channel)?;
+    let left_index = usize::try_from(current_index as i32 + step * pair_size).ok()?;
+
+    let left = channels.get(left_index)?.clone();
+    let right = if stereo {
+        Some(channels.get(left_index + 1)?.clone())
+    } else {
+        None
+    };
+
+    Some((left, right))
+}
+
+fn get_guide_delta(left: f32, right: f32, state: &State) -> Option<f32> {
+    let level = match state.guide_channel {
+        GuideChannel::Off => return None,
+        GuideChannel::Left => left,
+        GuideChannel::Right => right,
+    };
+
+    Some(level - state.reference_level).filter(|delta| delta.is_finite())
+}
+
+fn get_tone_analog_level_text(state: &State) -> String {
+    format_analog_level_for_display(state.reference_level, state.output_calibration, state.analog_unit)
+        .map(|(text, unit)| format!("= {text} {unit}"))
+        .unwrap_or_default()
+}
+
+fn format_device_calibration_for_display(calibration: Option<Calibration>) -> String {
+    calibration
+        .map(format_calibration_for_display)
+        .unwrap_or_else(|| CALIBRATION_MISSING_TEXT.to_string())
+}
+
+fn get_tolerance_from_selection(selection: &str) -> Option<f32> {
+    selection
+        .parse::<f32>()
+        .ok()
+        .filter(|tolerance| tolerance.is_finite() && *tolerance > 0.0)
+}
+
+fn format_tolerance_for_display(tolerance: Option<f32>) -> String {
+    match tolerance {
+        None => TOLERANCE_OFF_TEXT.to_string(),
+        Some(tolerance) if tolerance >= 1.0 => format!("{tolerance:.1}"),
+        Some(tolerance) => tolerance.to_string(),
     }
 }
 
@@ -794,4 +2829,390 @@ mod tests {
             assert_eq!(result, expected_result);
         }
     }
+
+    #[test]
+    fn return_signed_delta_value_for_display_without_a_negative_zero() {
+        assert_eq!(format_delta_value_for_display(-0.04, 1), "0.0");
+        assert_eq!(format_delta_value_for_display(0.07, 1), "+0.1");
+        assert_eq!(format_delta_value_for_display(-0.07, 2), "-0.07");
+        assert_eq!(format_delta_value_for_display(2.0, 1), "+2.0");
+    }
+
+    #[test]
+    fn classify_level_delta_against_the_unity_and_close_tolerances() {
+        assert_eq!(get_level_status_from_delta(0.1, 0.1, None), LevelStatus::Unity);
+        assert_eq!(get_level_status_from_delta(-0.2, 0.1, None), LevelStatus::Low);
+        assert_eq!(get_level_status_from_delta(0.2, 0.1, None), LevelStatus::High);
+        assert_eq!(get_level_status_from_delta(0.2, 0.1, Some(0.5)), LevelStatus::Close);
+        assert_eq!(get_level_status_from_delta(-0.6, 0.1, Some(0.5)), LevelStatus::Low);
+        assert_eq!(get_level_status_from_delta(0.06, 0.05, None), LevelStatus::High);
+    }
+
+    #[test]
+    fn level_readout_uses_the_precision_of_the_unity_tolerance() {
+        let state = State {
+            meter_delta_mode_active: true,
+            unity_tolerance: 0.05,
+            ..State::default()
+        };
+        let readout = get_level_readout(-0.07, &state);
+        assert_eq!(readout.text, "-0.07");
+        assert_eq!(readout.status, LevelStatus::Low);
+
+        let readout = get_level_readout(-0.07, &State { unity_tolerance: 0.1, ..state });
+        assert_eq!(readout.text, "-0.1");
+        assert_eq!(readout.status, LevelStatus::Unity);
+    }
+
+    #[test]
+    fn level_readout_is_neutral_in_peak_mode_and_without_signal() {
+        let state = State {
+            unity_tolerance: 0.1,
+            ..State::default()
+        };
+        assert_eq!(get_level_readout(-18.0, &state).status, LevelStatus::Neutral);
+
+        let delta_state = State {
+            meter_delta_mode_active: true,
+            ..state
+        };
+        let readout = get_level_readout(f32::NEG_INFINITY, &delta_state);
+        assert_eq!(readout.text, "-");
+        assert_eq!(readout.status, LevelStatus::Neutral);
+    }
+
+    #[test]
+    fn peak_level_readout_uses_the_input_calibration_and_analog_unit() {
+        let state = State {
+            analog_unit: AnalogUnit::Dbu,
+            input_calibration: Some(Calibration {
+                dbfs: 0.0,
+                dbu: 24.0,
+            }),
+            reference_level: -20.0,
+            output_calibration: Some(Calibration {
+                dbfs: -18.0,
+                dbu: 4.0,
+            }),
+            ..State::default()
+        };
+        let readout = get_level_readout(-20.0, &state);
+        assert_eq!((readout.text.as_str(), readout.unit.as_str()), ("+4.0", "dBu"));
+        assert_eq!(get_tone_analog_level_text(&state), "= +2.0 dBu");
+
+        let delta_readout = get_level_readout(
+            0.5,
+            &State {
+                meter_delta_mode_active: true,
+                unity_tolerance: 0.1,
+                ..state
+            },
+        );
+        assert_eq!((delta_readout.text.as_str(), delta_readout.unit.as_str()), ("+0.5", ""));
+
+        let uncalibrated = State {
+            input_calibration: None,
+            output_calibration: None,
+            ..state
+        };
+        assert_eq!(get_level_readout(-20.0, &uncalibrated).text, "-20.0");
+        assert_eq!(get_tone_analog_level_text(&uncalibrated), "");
+    }
+
+    #[test]
+    fn bar_meter_scale_marks_the_reference_and_tolerance_bands() {
+        let state = State {
+            reference_level: -18.0,
+            unity_tolerance: 0.5,
+            close_tolerance: Some(1.5),
+            ..State::default()
+        };
+        let scale = get_bar_meter_scale(&state);
+        assert!((scale.reference - 0.7).abs() < 1e-6);
+        assert!((scale.unity_high - scale.unity_low - 1.0 / 60.0).abs() < 1e-6);
+        assert!((scale.close_high - scale.close_low - 3.0 / 60.0).abs() < 1e-6);
+
+        let delta_state = State {
+            meter_delta_mode_active: true,
+            close_tolerance: None,
+            ..state
+        };
+        let delta_scale = get_bar_meter_scale(&delta_state);
+        assert_eq!(delta_scale.reference, 0.5);
+        assert_eq!(delta_scale.close_low, delta_scale.close_high);
+
+        let labels: Vec<String> = get_bar_meter_ticks(&delta_state)
+            .iter()
+            .map(|tick| tick.label.to_string())
+            .collect();
+        assert_eq!(labels.first().map(String::as_str), Some("-6"));
+        assert_eq!(labels[6], "0");
+        assert_eq!(labels.last().map(String::as_str), Some("+6"));
+    }
+
+    #[test]
+    fn secondary_level_shows_the_reading_the_current_mode_does_not() {
+        let state = State {
+            reference_level: -18.0,
+            ..State::default()
+        };
+        assert_eq!(get_secondary_level_text(-17.9, &state), "Delta +0.1 dB");
+        assert_eq!(get_secondary_level_text(f32::NEG_INFINITY, &state), "Delta - dB");
+
+        let delta_state = State {
+            meter_delta_mode_active: true,
+            ..state
+        };
+        assert_eq!(get_secondary_level_text(-17.94, &delta_state), "Peak -17.9 dBFS");
+    }
+
+    #[test]
+    fn step_to_the_next_whole_channel_pair() {
+        let channels: Vec<String> = (1..=6).map(|channel| channel.to_string()).collect();
+        let pair = |left, right: &str| Some((String::from(left), Some(right.to_string())));
+
+        assert_eq!(get_stepped_channel_pair(&channels, "1", true, 1), pair("3", "4"));
+        assert_eq!(get_stepped_channel_pair(&channels, "3", true, -1), pair("1", "2"));
+        assert_eq!(get_stepped_channel_pair(&channels, "5", true, 1), None);
+        assert_eq!(get_stepped_channel_pair(&channels, "1", true, -1), None);
+        assert_eq!(get_stepped_channel_pair(&channels, "5", false, 1), Some(("6".to_string(), None)));
+        assert_eq!(get_stepped_channel_pair(&channels, "9", true, 1), None);
+    }
+
+    #[test]
+    fn guide_follows_the_selected_channel_only() {
+        let state = State {
+            reference_level: -18.0,
+            ..State::default()
+        };
+        assert_eq!(get_guide_delta(-20.0, -16.0, &state), None);
+
+        let right_state = State {
+            guide_channel: get_item_from_selector_index(&GUIDE_CHANNELS, 2),
+            ..state
+        };
+        assert_eq!(get_guide_delta(-20.0, -16.0, &right_state), Some(2.0));
+        assert_eq!(get_guide_delta(-20.0, f32::NEG_INFINITY, &right_state), None);
+    }
+
+    #[test]
+    fn selector_index_round_trips_and_falls_back_to_the_default_item() {
+        for colour_theme in COLOUR_THEMES {
+            let index = get_selector_index_from_item(&COLOUR_THEMES, colour_theme);
+            assert_eq!(get_item_from_selector_index(&COLOUR_THEMES, index), colour_theme);
+        }
+        assert_eq!(get_item_from_selector_index(&COLOUR_THEMES, -1), ColourTheme::Dark);
+        assert_eq!(get_item_from_selector_index(&COLOUR_THEMES, 7), ColourTheme::Dark);
+    }
+
+    #[test]
+    fn tolerance_selections_round_trip_through_the_display_text() {
+        for tolerance in [Some(0.05), Some(0.1), Some(0.25), Some(0.5), Some(1.0), Some(2.0), None] {
+            let text = format_tolerance_for_display(tolerance);
+            assert_eq!(get_tolerance_from_selection(&text), tolerance);
+        }
+        assert_eq!(format_tolerance_for_display(Some(1.0)), "1.0");
+    }
+
+    #[test]
+    fn return_correct_waveform_from_selector_index() {
+        assert_eq!(get_waveform_from_selector_index(0), Waveform::Sine);
+        assert_eq!(get_waveform_from_selector_index(1), Waveform::Square);
+        assert_eq!(get_waveform_from_selector_index(2), Waveform::Sweep);
+        assert_eq!(get_waveform_from_selector_index(-1), Waveform::Sine);
+    }
+
+    #[test]
+    fn waveform_selector_index_round_trips_for_selectable_waveforms() {
+        for waveform in [Waveform::Sine, Waveform::Square, Waveform::Sweep] {
+            let index = get_selector_index_from_waveform(waveform);
+            assert_eq!(get_waveform_from_selector_index(index), waveform);
+        }
+        assert_eq!(get_selector_index_from_waveform(Waveform::Burst), 0);
+    }
+
+    #[test]
+    fn return_kilohertz_sweep_frequency_for_display_above_one_thousand_hertz() {
+        assert_eq!(format_sweep_frequency_for_display(440.4), "440");
+        assert_eq!(format_sweep_frequency_for_display(12500.0), "12.50k");
+    }
+
+    #[test]
+    fn return_dash_distortion_readout_when_there_is_no_measurement() {
+        let readout = get_distortion_readout_from_measurement(None);
+        assert_eq!(readout.thd_n_percent, "-");
+        assert_eq!(readout.sinad, "-");
+    }
+
+    #[test]
+    fn return_formatted_distortion_readout_from_measurement() {
+        let readout = get_distortion_readout_from_measurement(Some(DistortionMeasurement {
+            fundamental_frequency: 1000.04,
+            thd_n_percent: 0.012_345,
+            thd_n_db: -78.17,
+            sinad_db: 78.17,
+        }));
+        assert_eq!(readout.fundamental, "1000.0");
+        assert_eq!(readout.thd_n_percent, "0.0123");
+        assert_eq!(readout.thd_n_db, "-78.2");
+        assert_eq!(readout.sinad, "78.2");
+    }
+
+    #[test]
+    fn return_dc_offset_readout_in_dbfs_and_percent() {
+        let readout = get_dc_offset_readout(Some(-0.001));
+        assert_eq!(readout.dbfs, "-60.0");
+        assert_eq!(readout.percent, "-0.100");
+
+        let readout = get_dc_offset_readout(Some(0.0));
+        assert_eq!(readout.dbfs, "-");
+        assert_eq!(readout.percent, "+0.000");
+    }
+
+    #[test]
+    fn return_latched_clip_readout_from_count() {
+        let readout = get_clip_readout_from_count(ClipCount {
+            overs: 2,
+            full_scale_samples: 17,
+        });
+        assert!(readout.clipped);
+        assert_eq!(readout.overs, "2");
+        assert_eq!(readout.full_scale_samples, "17");
+
+        assert!(!get_clip_readout_from_count(ClipCount::default()).clipped);
+    }
+
+    #[test]
+    fn return_no_hum_warning_when_hum_is_below_the_threshold() {
+        let quiet_hum = HumMeasurement {
+            mains_frequency: 50.0,
+            relative_level_db: -85.0,
+        };
+        assert!(get_hum_warning_text(Some(quiet_hum), None).is_none());
+        assert!(get_hum_warning_text(None, None).is_none());
+    }
+
+    #[test]
+    fn return_hum_warning_with_the_worst_channel_level() {
+        let left = HumMeasurement {
+            mains_frequency: 60.0,
+            relative_level_db: -52.04,
+        };
+        let right = HumMeasurement {
+            mains_frequency: 60.0,
+            relative_level_db: -41.96,
+        };
+
+        let warning = get_hum_warning_text(Some(left), Some(right)).unwrap();
+        assert!(warning.starts_with("60 Hz mains hum at -42.0 dB"));
+        assert!(warning.contains(HUM_WARNING_BOTH_CHANNELS_TEXT));
+
+        let warning = get_hum_warning_text(None, Some(right)).unwrap();
+        assert!(warning.contains("on the right input."));
+    }
+
+    #[test]
+    fn return_spectrum_settings_from_ui_indexes() {
+        let settings = get_spectrum_settings_from_indexes(2, 3, 8);
+        assert_eq!(settings.fft_size, 4096);
+        assert_eq!(settings.window_function, WindowFunction::FlatTop);
+        assert_eq!(settings.averages, 8);
+
+        let defaults = get_spectrum_settings_from_indexes(-1, 99, 0);
+        assert_eq!(defaults.fft_size, SpectrumSettings::default().fft_size);
+        assert_eq!(defaults.window_function, WindowFunction::BlackmanHarris);
+        assert_eq!(defaults.averages, 1);
+    }
+
+    #[test]
+    fn spectrum_ui_indexes_round_trip_with_the_default_settings() {
+        let defaults = SpectrumSettings::default();
+        let settings = get_spectrum_settings_from_indexes(
+            get_spectrum_fft_size_index(defaults.fft_size),
+            get_spectrum_window_index(defaults.window_function),
+            defaults.averages as i32,
+        );
+        assert_eq!(settings, defaults);
+    }
+
+    #[test]
+    fn spectrum_path_skips_dc_and_is_empty_without_a_right_channel() {
+        let spectrum = Spectrum {
+            bin_width: 20.0,
+            left: vec![0.0, -20.0, -40.0],
+            right: Vec::new(),
+        };
+        let (left, right) = get_spectrum_path_commands(&spectrum, &get_spectrum_graph_scale(true));
+        assert_eq!(left, "M 0.0 142.9 L 100.3 285.7");
+        assert!(right.is_empty());
+    }
+
+    #[test]
+    fn flag_input_frequency_that_does_not_match_the_reference_tone() {
+        let state = State {
+            reference_frequency: 1000.0,
+            ..State::default()
+        };
+
+        let matching = get_input_frequency_readout(Some(1000.26), &state);
+        assert_eq!(matching.text, "1000.3");
+        assert!(!matching.mismatch);
+
+        let stray = get_input_frequency_readout(Some(50.0), &state);
+        assert!(stray.mismatch);
+
+        let silent = get_input_frequency_readout(None, &state);
+        assert_eq!(silent.text, "-");
+        assert!(!silent.mismatch);
+    }
+
+    #[test]
+    fn do_not_flag_input_frequency_while_sweeping() {
+        let state = State {
+            reference_frequency: 1000.0,
+            waveform: Waveform::Sweep,
+            ..State::default()
+        };
+
+        assert!(!get_input_frequency_readout(Some(50.0), &state).mismatch);
+    }
+
+    #[test]
+    fn do_not_flag_input_frequency_while_a_measurement_is_running() {
+        let state = State {
+            reference_frequency: 1000.0,
+            measurement_running: true,
+            ..State::default()
+        };
+
+        let readout = get_input_frequency_readout(Some(50.0), &state);
+        assert_eq!(readout.text, "50.0");
+        assert!(!readout.mismatch);
+    }
+
+    #[test]
+    fn return_inactive_stereo_image_readout_when_there_is_no_measurement() {
+        let readout = get_stereo_image_readout_from_measurement(None);
+        assert!(!readout.active);
+        assert_eq!(readout.correlation_text, "-");
+        assert_eq!(readout.balance_text, "-");
+    }
+
+    #[test]
+    fn return_signed_stereo_image_readout_from_measurement() {
+        let readout = get_stereo_image_readout_from_measurement(Some(StereoImage {
+            correlation: 0.987,
+            balance_db: -0.26,
+        }));
+        assert!(readout.active);
+        assert_eq!(readout.correlation_text, "+0.99");
+        assert_eq!(readout.balance_text, "-0.3");
+    }
+
+    #[test]
+    fn return_dash_level_value_for_display_until_measured_or_if_infinite() {
+        assert_eq!(format_level_value_for_display(None), "-");
+        assert_eq!(format_level_value_for_display(Some(f32::NEG_INFINITY)), "-");
+        assert_eq!(format_level_value_for_display(Some(-101.26)), "-101.3");
+    }
 }
@@ -0,0 +1,117 @@
+use std::time::{Duration, Instant};
+
+pub const PEAK_HOLD_DURATION: Duration = Duration::from_secs(2);
+
+const PEAK_MODE_RANGE: BarMeterRange = BarMeterRange {
+    minimum_level: -60.0,
+    maximum_level: 0.0,
+    tick_step: 6.0,
+};
+const DELTA_MODE_RANGE: BarMeterRange = BarMeterRange {
+    minimum_level: -6.0,
+    maximum_level: 6.0,
+    tick_step: 1.0,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BarMeterRange {
+    pub minimum_level: f32,
+    pub maximum_level: f32,
+    pub tick_step: f32,
+}
+
+impl BarMeterRange {
+    pub fn get_position_from_level(&self, level: f32) -> f32 {
+        if level.is_nan() {
+            return 0.0;
+        }
+
+        ((level - self.minimum_level) / (self.maximum_level - self.minimum_level)).clamp(0.0, 1.0)
+    }
+
+    pub fn get_ticks(&self) -> Vec<(f32, f32)> {
+        let number_of_ticks =
+            ((self.maximum_level - self.minimum_level) / self.tick_step).round() as usize;
+
+        (0..=number_of_ticks)
+            .map(|index| {
+                let level = self.minimum_level + index as f32 * self.tick_step;
+                (self.get_position_from_level(level), level)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PeakHold {
+    level: f32,
+    held_at: Option<Instant>,
+}
+
+impl Default for PeakHold {
+    fn default() -> Self {
+        Self {
+            level: f32::NEG_INFINITY,
+            held_at: None,
+        }
+    }
+}
+
+impl PeakHold {
+    pub fn update(&mut self, level: f32, now: Instant) -> f32 {
+        let hold_expired = self
+            .held_at
+            .is_none_or(|held_at| now.duration_since(held_at) >= PEAK_HOLD_DURATION);
+
+        if level >= self.level || hold_expired {
+            self.level = level;
+            self.held_at = Some(now);
+        }
+
+        self.level
+    }
+}
+
+pub fn get_bar_meter_range(delta_mode_active: bool) -> BarMeterRange {
+    if delta_mode_active {
+        DELTA_MODE_RANGE
+    } else {
+        PEAK_MODE_RANGE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn return_clamped_position_from_level() {
+        let range = get_bar_meter_range(false);
+        assert_eq!(range.get_position_from_level(-30.0), 0.5);
+        assert_eq!(range.get_position_from_level(f32::NEG_INFINITY), 0.0);
+        assert_eq!(range.get_position_from_level(3.0), 1.0);
+        assert_eq!(range.get_position_from_level(f32::NAN), 0.0);
+        assert_eq!(get_bar_meter_range(true).get_position_from_level(0.0), 0.5);
+    }
+
+    #[test]
+    fn return_one_tick_per_step_including_both_ends() {
+        let ticks = get_bar_meter_range(true).get_ticks();
+        assert_eq!(ticks.len(), 13);
+        assert_eq!(ticks.first(), Some(&(0.0, -6.0)));
+        assert_eq!(ticks.last(), Some(&(1.0, 6.0)));
+        assert_eq!(get_bar_meter_range(false).get_ticks().len(), 11);
+    }
+
+    #[test]
+    fn hold_the_peak_until_the_hold_time_runs_out() {
+        let start = Instant::now();
+        let mut peak_hold = PeakHold::default();
+
+        assert_eq!(peak_hold.update(-10.0, start), -10.0);
+        assert_eq!(peak_hold.update(-20.0, start + Duration::from_secs(1)), -10.0);
+        assert_eq!(peak_hold.update(-5.0, start + Duration::from_millis(1500)), -5.0);
+        assert_eq!(peak_hold.update(-20.0, start + Duration::from_millis(3000)), -5.0);
+        assert_eq!(peak_hold.update(-20.0, start + Duration::from_millis(3500)), -20.0);
+    }
+}
@@ -0,0 +1,142 @@
+pub const GRAPH_VIEWBOX_SIZE: f32 = 1000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GraphScale {
+    pub minimum_frequency: f32,
+    pub maximum_frequency: f32,
+    pub minimum_level: f32,
+    pub maximum_level: f32,
+    pub logarithmic_frequency: bool,
+}
+
+impl GraphScale {
+    fn get_x_from_frequency(&self, frequency: f32) -> f32 {
+        let position = if self.logarithmic_frequency {
+            let octaves_from_minimum =
+                (frequency.max(f32::MIN_POSITIVE) / self.minimum_frequency).log2();
+            let total_octaves = (self.maximum_frequency / self.minimum_frequency).log2();
+            octaves_from_minimum / total_octaves
+        } else {
+            (frequency - self.minimum_frequency) / (self.maximum_frequency - self.minimum_frequency)
+        };
+        position.clamp(0.0, 1.0) * GRAPH_VIEWBOX_SIZE
+    }
+
+    fn get_y_from_level(&self, level: f32) -> f32 {
+        let position = (self.maximum_level - level) / (self.maximum_level - self.minimum_level);
+        position.clamp(0.0, 1.0) * GRAPH_VIEWBOX_SIZE
+    }
+}
+
+pub fn get_path_commands_from_points(points: &[(f32, f32)], scale: &GraphScale) -> String {
+    points
+        .iter()
+        .filter(|(frequency, level)| frequency.is_finite() && level.is_finite())
+        .enumerate()
+        .map(|(index, (frequency, level))| {
+            let command = if index == 0 { "M" } else { "L" };
+            format!(
+                "{command} {:.1} {:.1}",
+                scale.get_x_from_frequency(*frequency),
+                scale.get_y_from_level(*level)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+pub fn get_peak_points_per_graph_column(points: &[(f32, f32)], scale: &GraphScale) -> Vec<(f32, f32)> {
+    let mut peak_points: Vec<(f32, f32)> = Vec::new();
+    let mut previous_column = None;
+
+    for (frequency, level) in points.iter().copied() {
+        if frequency < scale.minimum_frequency || frequency > scale.maximum_frequency {
+            continue;
+        }
+
+        let column = scale.get_x_from_frequency(frequency).round() as i32;
+        match peak_points.last_mut() {
+            Some(last) if previous_column == Some(column) => {
+                if level > last.1 {
+                    *last = (frequency, level);
+                }
+            }
+            _ => peak_points.push((frequency, level)),
+        }
+        previous_column = Some(column);
+    }
+
+    peak_points
+}
+
+pub fn get_grid_path_commands(
+    scale: &GraphScale,
+    frequency_lines: &[f32],
+    level_lines: &[f32],
+) -> String {
+    let vertical_lines = frequency_lines.iter().map(|frequency| {
+        let x = scale.get_x_from_frequency(*frequency);
+        format!("M {x:.1} 0 L {x:.1} {GRAPH_VIEWBOX_SIZE:.1}")
+    });
+
+    let horizontal_lines = level_lines.iter().map(|level| {
+        let y = scale.get_y_from_level(*level);
+        format!("M 0 {y:.1} L {GRAPH_VIEWBOX_SIZE:.1} {y:.1}")
+    });
+
+    vertical_lines
+        .chain(horizontal_lines)
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_SCALE: GraphScale = GraphScale {
+        minimum_frequency: 10.0,
+        maximum_frequency: 1000.0,
+        minimum_level: -1.0,
+        maximum_level: 1.0,
+        logarithmic_frequency: true,
+    };
+
+    #[test]
+    fn return_path_commands_with_log_frequency_and_clamped_level() {
+        let points = [(10.0, 1.0), (100.0, 0.0), (1000.0, -5.0)];
+        let commands = get_path_commands_from_points(&points, &TEST_SCALE);
+        assert_eq!(commands, "M 0.0 0.0 L 500.0 500.0 L 1000.0 1000.0");
+    }
+
+    #[test]
+    fn skip_points_that_are_not_finite() {
+        let points = [(10.0, f32::NEG_INFINITY), (100.0, 0.0)];
+        let commands = get_path_commands_from_points(&points, &TEST_SCALE);
+        assert_eq!(commands, "M 500.0 500.0");
+    }
+
+    #[test]
+    fn return_path_commands_with_linear_frequency() {
+        let scale = GraphScale {
+            logarithmic_frequency: false,
+            ..TEST_SCALE
+        };
+        let points = [(10.0, 1.0), (505.0, 0.0), (1000.0, -1.0)];
+        let commands = get_path_commands_from_points(&points, &scale);
+        assert_eq!(commands, "M 0.0 0.0 L 500.0 500.0 L 1000.0 1000.0");
+    }
+
+    #[test]
+    fn keep_only_the_loudest_point_in_each_graph_column() {
+        let points = [(5.0, 0.0), (100.0, -0.5), (100.01, 0.5), (100.02, 0.2), (1000.0, 0.0)];
+        let peak_points = get_peak_points_per_graph_column(&points, &TEST_SCALE);
+        assert_eq!(peak_points, vec![(100.01, 0.5), (1000.0, 0.0)]);
+    }
+
+    #[test]
+    fn return_grid_lines_for_frequencies_and_levels() {
+        let commands = get_grid_path_commands(&TEST_SCALE, &[100.0], &[0.0]);
+        assert_eq!(commands, "M 500.0 0 L 500.0 1000.0 M 0 500.0 L 1000.0 500.0");
+    }
+}
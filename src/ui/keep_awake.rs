@@ -0,0 +1,137 @@
+use crate::errors::LocalError;
+#[cfg(target_os = "macos")]
+use std::process::{Child, Command, Stdio};
+#[cfg(all(unix, not(target_os = "macos")))]
+use zbus::blocking::Connection;
+
+#[cfg(target_os = "windows")]
+const ES_CONTINUOUS: u32 = 0x8000_0000;
+#[cfg(target_os = "windows")]
+const ES_SYSTEM_REQUIRED: u32 = 0x0000_0001;
+#[cfg(target_os = "windows")]
+const ES_DISPLAY_REQUIRED: u32 = 0x0000_0002;
+
+#[cfg(all(unix, not(target_os = "macos")))]
+const SCREEN_SAVER_SERVICE: &str = "org.freedesktop.ScreenSaver";
+#[cfg(all(unix, not(target_os = "macos")))]
+const SCREEN_SAVER_PATH: &str = "/org/freedesktop/ScreenSaver";
+#[cfg(all(unix, not(target_os = "macos")))]
+const SCREEN_SAVER_INTERFACE: &str = "org.freedesktop.ScreenSaver";
+#[cfg(all(unix, not(target_os = "macos")))]
+const INHIBIT_APPLICATION_NAME: &str = "Stereo Unity Gain";
+#[cfg(all(unix, not(target_os = "macos")))]
+const INHIBIT_REASON: &str = "Showing the fullscreen level meters";
+
+// SAFETY: SetThreadExecutionState is declared with the signature documented for kernel32.
+#[cfg(target_os = "windows")]
+#[link(name = "kernel32")]
+unsafe extern "system" {
+    fn SetThreadExecutionState(flags: u32) -> u32;
+}
+
+pub struct KeepAwake {
+    #[cfg(target_os = "macos")]
+    inhibitor: Child,
+    #[cfg(all(unix, not(target_os = "macos")))]
+    inhibitor: ScreenSaverInhibitor,
+}
+
+impl KeepAwake {
+    pub fn start() -> Result<Self, LocalError> {
+        #[cfg(target_os = "windows")]
+        {
+            let flags = ES_CONTINUOUS | ES_SYSTEM_REQUIRED | ES_DISPLAY_REQUIRED;
+            // SAFETY: the call takes a plain flag value and touches no memory owned by this process.
+            if unsafe { SetThreadExecutionState(flags) } == 0 {
+                return Err(LocalError::KeepAwake("SetThreadExecutionState failed".to_string()));
+            }
+        }
+
+        Ok(Self {
+            #[cfg(target_os = "macos")]
+            inhibitor: get_inhibitor_command(std::process::id())
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .map_err(|err| LocalError::KeepAwake(err.to_string()))?,
+            #[cfg(all(unix, not(target_os = "macos")))]
+            inhibitor: ScreenSaverInhibitor::start()?,
+        })
+    }
+}
+
+impl Drop for KeepAwake {
+    fn drop(&mut self) {
+        #[cfg(target_os = "macos")]
+        {
+            let _ = self.inhibitor.kill();
+            let _ = self.inhibitor.wait();
+        }
+
+        #[cfg(all(unix, not(target_os = "macos")))]
+        self.inhibitor.stop();
+
+        // SAFETY: the call takes a plain flag value and touches no memory owned by this process.
+        #[cfg(target_os = "windows")]
+        unsafe {
+            SetThreadExecutionState(ES_CONTINUOUS);
+        }
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+struct ScreenSaverInhibitor {
+    connection: Connection,
+    cookie: u32,
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+impl ScreenSaverInhibitor {
+    fn start() -> Result<Self, LocalError> {
+        let connection = Connection::session().map_err(|err| LocalError::KeepAwake(err.to_string()))?;
+
+        let cookie = connection
+            .call_method(
+                Some(SCREEN_SAVER_SERVICE),
+                SCREEN_SAVER_PATH,
+                Some(SCREEN_SAVER_INTERFACE),
+                "Inhibit",
+                &(INHIBIT_APPLICATION_NAME, INHIBIT_REASON),
+            )
+            .and_then(|reply| reply.body().deserialize::<u32>())
+            .map_err(|err| LocalError::KeepAwake(err.to_string()))?;
+
+        Ok(Self { connection, cookie })
+    }
+
+    fn stop(&self) {
+        let _ = self.connection.call_method(
+            Some(SCREEN_SAVER_SERVICE),
+            SCREEN_SAVER_PATH,
+            Some(SCREEN_SAVER_INTERFACE),
+            "UnInhibit",
+            &(self.cookie,),
+        );
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn get_inhibitor_command(process_id: u32) -> Command {
+    let mut command = Command::new("caffeinate");
+    command.args(["-d", "-i", "-w", &process_id.to_string()]);
+    command
+}
+
+#[cfg(all(test, target_os = "macos"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caffeinate_exits_with_the_application_on_macos() {
+        let command = get_inhibitor_command(42);
+        let arguments: Vec<_> = command.get_args().collect();
+        assert_eq!(command.get_program(), "caffeinate");
+        assert_eq!(arguments[arguments.len() - 2..], ["-w", "42"]);
+    }
+}
@@ -0,0 +1,261 @@
+use super::graph::{GraphScale, get_grid_path_commands};
+use super::{
+    CheckResult, CrosstalkReadout, LatencyReadout, NoiseFloorReadout, PolarityReadout, UnityCheckRow,
+    format_level_value_for_display, format_peak_delta_values_for_display,
+};
+use crate::measurement::crosstalk::ChannelCrosstalk;
+use crate::measurement::gain_drift::GainDriftLog;
+use crate::measurement::latency::LatencyResult;
+use crate::measurement::noise_floor::ChannelNoiseFloor;
+use crate::measurement::polarity::PolarityResult;
+use crate::measurement::unity_check::{UnityCheckResult, is_delta_within_tolerance};
+use slint::SharedString;
+
+const GAIN_DRIFT_MINIMUM_LEVEL_RANGE: f32 = 0.5;
+const GAIN_DRIFT_LEVEL_RANGE_STEP: f32 = 0.5;
+const GAIN_DRIFT_TIME_GRID_DIVISIONS: usize = 4;
+
+pub fn get_check_result_from_deviation(deviation: Option<f32>, tolerance: f32) -> CheckResult {
+    match deviation {
+        None => CheckResult::Pending,
+        Some(deviation) if deviation <= tolerance => CheckResult::Pass,
+        Some(_) => CheckResult::Fail,
+    }
+}
+
+pub fn format_drift_for_display(drift: Option<f32>) -> String {
+    match drift {
+        Some(drift) if drift.is_finite() => format!("{:.2}", drift),
+        _ => "-".to_string(),
+    }
+}
+
+pub fn format_deviation_for_display(deviation: Option<f32>) -> String {
+    match deviation {
+        Some(deviation) if deviation.is_finite() => format!("±{:.2}", deviation),
+        _ => "-".to_string(),
+    }
+}
+
+pub fn get_unity_check_rows_from_result(unity_check_result: &UnityCheckResult) -> Vec<UnityCheckRow> {
+    let tolerance = unity_check_result.tolerance;
+
+    unity_check_result
+        .steps
+        .iter()
+        .map(|step| UnityCheckRow {
+            frequency: SharedString::from(format_step_frequency_for_display(step.frequency)),
+            left: SharedString::from(format_peak_delta_values_for_display(step.left_delta)),
+            right: SharedString::from(
+                step.right_delta
+                    .map_or("-".to_string(), format_peak_delta_values_for_display),
+            ),
+            left_result: get_check_result_from_delta(Some(step.left_delta), tolerance),
+            right_result: get_check_result_from_delta(step.right_delta, tolerance),
+        })
+        .collect()
+}
+
+pub fn get_check_result_from_delta(delta: Option<f32>, tolerance: f32) -> CheckResult {
+    match delta {
+        None => CheckResult::Pending,
+        Some(delta) if is_delta_within_tolerance(delta, tolerance) => CheckResult::Pass,
+        Some(_) => CheckResult::Fail,
+    }
+}
+
+pub fn format_step_frequency_for_display(frequency: f32) -> String {
+    if frequency >= 1000.0 {
+        format!("{}k", frequency / 1000.0)
+    } else {
+        format!("{}", frequency)
+    }
+}
+
+pub fn get_gain_drift_graph_scale(gain_drift_log: &GainDriftLog) -> GraphScale {
+    let largest_delta = gain_drift_log
+        .points
+        .iter()
+        .flat_map(|point| [Some(point.left_delta), point.right_delta])
+        .flatten()
+        .filter(|delta| delta.is_finite())
+        .map(f32::abs)
+        .fold(0.0, f32::max);
+    let level_range = ((largest_delta / GAIN_DRIFT_LEVEL_RANGE_STEP).ceil()
+        * GAIN_DRIFT_LEVEL_RANGE_STEP)
+        .max(GAIN_DRIFT_MINIMUM_LEVEL_RANGE);
+
+    GraphScale {
+        minimum_frequency: 0.0,
+        maximum_frequency: (gain_drift_log.get_elapsed_in_seconds() as f32).max(1.0),
+        minimum_level: -level_range,
+        maximum_level: level_range,
+        logarithmic_frequency: false,
+    }
+}
+
+pub fn get_gain_drift_grid_commands(scale: &GraphScale) -> String {
+    let time_lines = (1..GAIN_DRIFT_TIME_GRID_DIVISIONS)
+        .map(|division| {
+            scale.maximum_frequency * division as f32 / GAIN_DRIFT_TIME_GRID_DIVISIONS as f32
+        })
+        .collect::<Vec<f32>>();
+    let level_lines = [scale.minimum_level / 2.0, 0.0, scale.maximum_level / 2.0];
+
+    get_grid_path_commands(scale, &time_lines, &level_lines)
+}
+
+pub fn get_noise_floor_readout_from_channel(channel: Option<ChannelNoiseFloor>) -> NoiseFloorReadout {
+    let Some(channel) = channel else {
+        return NoiseFloorReadout {
+            noise_floor: SharedString::from("-"),
+            a_weighted_noise_floor: SharedString::from("-"),
+            signal_to_noise: SharedString::from("-"),
+            a_weighted_signal_to_noise: SharedString::from("-"),
+            dynamic_range: SharedString::from("-"),
+            a_weighted_dynamic_range: SharedString::from("-"),
+        };
+    };
+
+    NoiseFloorReadout {
+        noise_floor: SharedString::from(format_level_value_for_display(Some(channel.noise_floor))),
+        a_weighted_noise_floor: SharedString::from(format_level_value_for_display(Some(
+            channel.a_weighted_noise_floor,
+        ))),
+        signal_to_noise: SharedString::from(format_level_value_for_display(
+            channel.get_signal_to_noise_ratio(),
+        )),
+        a_weighted_signal_to_noise: SharedString::from(format_level_value_for_display(
+            channel.get_a_weighted_signal_to_noise_ratio(),
+        )),
+        dynamic_range: SharedString::from(format_level_value_for_display(Some(
+            channel.get_dynamic_range(),
+        ))),
+        a_weighted_dynamic_range: SharedString::from(format_level_value_for_display(Some(
+            channel.get_a_weighted_dynamic_range(),
+        ))),
+    }
+}
+
+pub fn get_crosstalk_readout_from_channel(channel: Option<ChannelCrosstalk>) -> CrosstalkReadout {
+    CrosstalkReadout {
+        driven_level: SharedString::from(format_level_value_for_display(
+            channel.map(|channel| channel.driven_level),
+        )),
+        leakage_level: SharedString::from(format_level_value_for_display(
+            channel.map(|channel| channel.leakage_level),
+        )),
+        crosstalk: SharedString::from(format_level_value_for_display(
+            channel.map(|channel| channel.get_crosstalk()),
+        )),
+    }
+}
+
+pub fn get_latency_readout_from_result(latency_result: &LatencyResult) -> LatencyReadout {
+    let format_milliseconds = |samples: Option<f32>| {
+        SharedString::from(samples.map_or("-".to_string(), |value| {
+            format!("{:.2}", latency_result.get_milliseconds_from_samples(value))
+        }))
+    };
+    let format_samples = |samples: Option<f32>| {
+        SharedString::from(samples.map_or("-".to_string(), |value| format!("{:.0}", value)))
+    };
+
+    LatencyReadout {
+        mean_milliseconds: format_milliseconds(latency_result.get_mean()),
+        mean_samples: format_samples(latency_result.get_mean()),
+        minimum_milliseconds: format_milliseconds(latency_result.get_minimum()),
+        minimum_samples: format_samples(latency_result.get_minimum()),
+        maximum_milliseconds: format_milliseconds(latency_result.get_maximum()),
+        maximum_samples: format_samples(latency_result.get_maximum()),
+        jitter_milliseconds: format_milliseconds(latency_result.get_jitter()),
+        jitter_samples: format_samples(latency_result.get_jitter()),
+    }
+}
+
+pub fn get_polarity_readout_from_result(polarity_result: &PolarityResult) -> PolarityReadout {
+    let format_offset = |offset: Option<f32>, precision: usize| {
+        SharedString::from(offset.map_or("-".to_string(), |value| format!("{:.*}", precision, value)))
+    };
+
+    PolarityReadout {
+        left_result: get_check_result_from_polarity(polarity_result.left_inverted),
+        right_result: get_check_result_from_polarity(polarity_result.right_inverted),
+        offset_samples: format_offset(polarity_result.inter_channel_offset_in_samples, 2),
+        offset_microseconds: format_offset(
+            polarity_result.get_inter_channel_offset_in_microseconds(),
+            1,
+        ),
+        phase_degrees: format_offset(polarity_result.get_inter_channel_phase_in_degrees(), 1),
+    }
+}
+
+pub fn get_check_result_from_polarity(inverted: Option<bool>) -> CheckResult {
+    match inverted {
+        None => CheckResult::Pending,
+        Some(false) => CheckResult::Pass,
+        Some(true) => CheckResult::Fail,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::measurement::gain_drift::GainDriftPoint;
+
+    #[test]
+    fn return_check_result_from_deviation_and_tolerance() {
+        assert_eq!(get_check_result_from_deviation(None, 0.1), CheckResult::Pending);
+        assert_eq!(get_check_result_from_deviation(Some(0.05), 0.1), CheckResult::Pass);
+        assert_eq!(get_check_result_from_deviation(Some(0.25), 0.1), CheckResult::Fail);
+    }
+
+    #[test]
+    fn return_step_frequency_for_display_without_trailing_zeros() {
+        assert_eq!(format_step_frequency_for_display(31.5), "31.5");
+        assert_eq!(format_step_frequency_for_display(125.0), "125");
+        assert_eq!(format_step_frequency_for_display(16000.0), "16k");
+    }
+
+    #[test]
+    fn return_dash_drift_for_display_until_there_are_points() {
+        assert_eq!(format_drift_for_display(None), "-");
+        assert_eq!(format_drift_for_display(Some(0.126)), "0.13");
+    }
+
+    #[test]
+    fn gain_drift_graph_scale_grows_in_half_db_steps_to_fit_the_log() {
+        let mut log = GainDriftLog::default();
+        let scale = get_gain_drift_graph_scale(&log);
+        assert_eq!(scale.maximum_level, 0.5);
+        assert_eq!(scale.maximum_frequency, 1.0);
+
+        log.points.push(GainDriftPoint {
+            elapsed_in_seconds: 3600.0,
+            left_delta: 0.2,
+            right_delta: Some(-1.2),
+        });
+        let scale = get_gain_drift_graph_scale(&log);
+        assert_eq!(scale.minimum_level, -1.5);
+        assert_eq!(scale.maximum_level, 1.5);
+        assert_eq!(scale.maximum_frequency, 3600.0);
+    }
+
+    #[test]
+    fn return_latency_readout_in_milliseconds_and_samples() {
+        let readout = get_latency_readout_from_result(&LatencyResult {
+            sample_rate: 48000.0,
+            latencies_in_samples: vec![480.0, 504.0],
+        });
+        assert_eq!(readout.mean_milliseconds, "10.25");
+        assert_eq!(readout.mean_samples, "492");
+        assert_eq!(readout.jitter_samples, "12");
+    }
+
+    #[test]
+    fn return_failed_check_result_for_inverted_polarity() {
+        assert_eq!(get_check_result_from_polarity(None), CheckResult::Pending);
+        assert_eq!(get_check_result_from_polarity(Some(false)), CheckResult::Pass);
+        assert_eq!(get_check_result_from_polarity(Some(true)), CheckResult::Fail);
+    }
+}